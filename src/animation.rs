@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use crate::{
+    DEAL_ANIMATION_TIME_MS, FLIP_ANIMATION_TIME_MS,
+    game::GameState,
+    player::{Player, PlayerState},
+};
+
+/// What a card is doing while it animates
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Motion {
+    Deal,                         // slides from the deck to its slot in the dealer row
+    Flip,                         // turns face up in its slot in the played row
+    Reveal { hand_index: usize }, // moves from the hidden hand to the played row, then turns face up
+}
+
+/// A single card moving or turning over
+///
+#[derive(Debug, Clone)]
+pub struct Animation {
+    pub owner: Player,
+    pub index: usize, // position of the card in its row
+    pub motion: Motion,
+    elapsed: Duration,
+    duration: Duration,
+}
+
+impl Animation {
+    fn new(owner: Player, index: usize, motion: Motion) -> Self {
+        let duration = match motion {
+            Motion::Deal => DEAL_ANIMATION_TIME_MS,
+            Motion::Flip => FLIP_ANIMATION_TIME_MS,
+            Motion::Reveal { .. } => DEAL_ANIMATION_TIME_MS + FLIP_ANIMATION_TIME_MS,
+        };
+
+        Self {
+            owner,
+            index,
+            motion,
+            elapsed: Duration::ZERO,
+            duration: Duration::from_millis(duration),
+        }
+    }
+
+    /// How far along the animation is, from 0.0 to 1.0
+    ///
+    pub fn progress(&self) -> f32 {
+        (self.elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    fn is_done(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+/// Cards a side had the last time the game state was looked at
+///
+#[derive(Debug, Default)]
+struct Seen {
+    dealer: usize,
+    played: usize,
+    hand: Vec<bool>, // which hand slots held a card
+}
+
+impl Seen {
+    fn of(side: &PlayerState) -> Self {
+        Self {
+            dealer: side.dealer_row.len(),
+            played: side.played_row.len(),
+            hand: side.hand.iter().map(Option::is_some).collect(),
+        }
+    }
+}
+
+/// Card animations for the board.
+/// New cards are spotted by comparing the game state between ticks, so the game
+/// itself never waits on an animation and input keeps working while they play.
+///
+#[derive(Debug)]
+pub struct Animations {
+    pub enabled: bool,
+    active: Vec<Animation>,
+    player: Seen,
+    opponent: Seen,
+}
+
+impl Animations {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            active: vec![],
+            player: Seen::default(),
+            opponent: Seen::default(),
+        }
+    }
+
+    /// Advance running animations by dt and start new ones for cards dealt or played since last time.
+    /// Returns whether the board needs redrawing, which includes the frame an animation finishes
+    ///
+    pub fn update(&mut self, state: &GameState, dt: Duration) -> bool {
+        let was_animating = self.is_animating();
+
+        for animation in &mut self.active {
+            animation.elapsed += dt;
+        }
+        self.active.retain(|animation| !animation.is_done());
+
+        let player = Seen::of(&state.seats[Player::Player]);
+        let opponent = Seen::of(&state.seats[Player::Opponent]);
+
+        // Rows only shrink when a round or game starts over, so drop anything in flight
+        let cleared = player.dealer < self.player.dealer
+            || player.played < self.player.played
+            || opponent.dealer < self.opponent.dealer
+            || opponent.played < self.opponent.played;
+
+        if cleared || !self.enabled {
+            self.active.clear();
+        } else {
+            let sides = [
+                (Player::Player, &self.player, &player),
+                (Player::Opponent, &self.opponent, &opponent),
+            ];
+            let started = sides
+                .into_iter()
+                .flat_map(|(owner, before, after)| Self::started(state, owner, before, after))
+                .collect::<Vec<_>>();
+            self.active.extend(started);
+        }
+
+        self.player = player;
+        self.opponent = opponent;
+
+        was_animating || self.is_animating()
+    }
+
+    // Animations for every card that appeared between `before` and `after`
+    fn started(
+        state: &GameState,
+        owner: Player,
+        before: &Seen,
+        after: &Seen,
+    ) -> impl Iterator<Item = Animation> {
+        let deals =
+            (before.dealer..after.dealer).map(move |i| Animation::new(owner, i, Motion::Deal));
+
+        // Cards from a hidden hand come out of it face down, a face up hand's are already showing
+        let emptied_slot = before
+            .hand
+            .iter()
+            .zip(&after.hand)
+            .position(|(was, is)| *was && !*is);
+        let play_motion = match emptied_slot {
+            Some(hand_index) if !state.shows_hand(owner) => Motion::Reveal { hand_index },
+            _ => Motion::Flip,
+        };
+        let plays =
+            (before.played..after.played).map(move |i| Animation::new(owner, i, play_motion));
+
+        deals.chain(plays)
+    }
+
+    /// Jump every running animation to its end
+    ///
+    pub fn skip(&mut self) {
+        self.active.clear();
+    }
+
+    pub fn is_animating(&self) -> bool {
+        !self.active.is_empty()
+    }
+
+    /// Animation for a card in `owner`'s dealer row, if it's still moving
+    ///
+    pub fn dealer(&self, owner: Player, index: usize) -> Option<&Animation> {
+        self.find(owner, index, |motion| motion == Motion::Deal)
+    }
+
+    /// Animation for a card in `owner`'s played row, if it's still moving
+    ///
+    pub fn played(&self, owner: Player, index: usize) -> Option<&Animation> {
+        self.find(owner, index, |motion| motion != Motion::Deal)
+    }
+
+    fn find(
+        &self,
+        owner: Player,
+        index: usize,
+        row: impl Fn(Motion) -> bool,
+    ) -> Option<&Animation> {
+        self.active.iter().find(|animation| {
+            animation.owner == owner && animation.index == index && row(animation.motion)
+        })
+    }
+}
+
+impl Default for Animations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
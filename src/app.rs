@@ -1,34 +1,96 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossterm::event::KeyCode;
 
 use crate::{
+    ANIMATION_FRAME_MS,
+    audio::{AudioBackend, Cue, SilentBackend},
     board::BoardView,
+    bot::Bot,
+    canvas::Canvas,
+    chat::Chat,
     config::Config,
-    frame::Frame,
+    crash::CrashContext,
+    debug::DebugOverlay,
+    frame::{Frame, clear_frame},
     game::GameState,
+    how_to_play::{HowToPlayEvent, HowToPlayState},
+    layout::{Align, Rect},
     menu::{MenuEvent, MenuItem, MenuState},
+    net::NetSession,
+    render::RenderStats,
     screen::Screen,
+    settings::{Opponent, Settings, SettingsEvent, SettingsState},
+    theme::Role,
+    tutorial::TutorialState,
 };
 
 pub struct App {
     pub config: Config,
+    pub settings: Settings,
     screen: Screen,
     board_view: BoardView,
+    chat: Chat,
+    bot: Option<Bot>, // can be picked as the opponent in place of the computer, if given
+    debug: DebugOverlay,
+    audio: Box<dyn AudioBackend>,
+    dirty: bool, // needs drawing again
 }
 
 impl App {
     pub fn new(config: Config) -> Self {
+        Self::with_settings(config, Settings::new())
+    }
+
+    /// Start with the given settings rather than ones picked up from the environment
+    ///
+    pub fn with_settings(config: Config, settings: Settings) -> Self {
+        Self::with_audio(config, settings, Box::new(SilentBackend))
+    }
+
+    /// Start with the given settings, playing sounds through `audio`
+    ///
+    pub fn with_audio(config: Config, settings: Settings, audio: Box<dyn AudioBackend>) -> Self {
+        let mut board_view = BoardView::new(config.clone());
+        board_view.theme = settings.theme;
+        board_view.card_style = settings.card_style;
+        board_view.animations.enabled = settings.animations;
+
         Self {
-            config: config.clone(),
+            config,
+            settings,
             screen: Screen::StartMenu {
                 menu_state: MenuState::new(),
             },
-            board_view: BoardView::new(config),
+            board_view,
+            chat: Chat::new(),
+            bot: None,
+            debug: DebugOverlay::new(),
+            audio,
+            dirty: true,
         }
     }
 
     pub fn handle_key(&mut self, key: KeyCode) {
+        // Most keys change something on screen, so always redraw after one
+        self.dirty = true;
+
+        // The debug overlay works on every screen, so it's checked before any routing
+        if key == KeyCode::F(12) {
+            self.debug.toggle();
+            return;
+        }
+
+        // An open chat line takes every key, so typing can't make a move
+        if self.allows_chat()
+            && let Some(chat_action) = self.chat.handle_input(key)
+        {
+            if let Some(message) = self.chat.apply_action(chat_action) {
+                self.say(message);
+            }
+            return;
+        }
+
         match &mut self.screen {
             // Route the Menu inputs only to Menu
             Screen::StartMenu { menu_state } => {
@@ -39,6 +101,37 @@ impl App {
                 }
             }
 
+            // Route the rules screen inputs, returning to the menu on exit
+            Screen::HowToPlay { how_to_play } => {
+                if let Some(action) = how_to_play.handle_input(key)
+                    && let Some(HowToPlayEvent::Exit) = how_to_play.apply_action(action)
+                {
+                    self.screen = Screen::StartMenu {
+                        menu_state: MenuState::new(),
+                    }
+                }
+            }
+
+            // Route the settings inputs, applying changes as they're made
+            Screen::Settings { settings_state } => {
+                if let Some(action) = settings_state.handle_input(key) {
+                    let event = settings_state.apply_action(action, &mut self.settings);
+                    // There's only a bot to pick if one was given on the command line
+                    if self.bot.is_none() {
+                        self.settings.opponent = Opponent::Computer;
+                    }
+                    self.board_view.theme = self.settings.theme;
+                    self.board_view.card_style = self.settings.card_style;
+                    self.board_view.animations.enabled = self.settings.animations;
+
+                    if let Some(SettingsEvent::Exit) = event {
+                        self.screen = Screen::StartMenu {
+                            menu_state: MenuState::new(),
+                        }
+                    }
+                }
+            }
+
             // Route the game inputs to game_state
             Screen::InGame { game_state } => if let KeyCode::Char(c) = key {
                 match c {
@@ -47,6 +140,7 @@ impl App {
                             menu_state: MenuState::new(),
                         }
                     }
+                    ' ' => self.board_view.animations.skip(),
                     _ => {
                         if let Some(game_action) = game_state.handle_game_input(c) {
                             game_state.apply_game_action(game_action);
@@ -54,37 +148,333 @@ impl App {
                     }
                 }
             },
+
+            // The tutorial vets every game input before it reaches the game
+            Screen::Tutorial { tutorial } => if let KeyCode::Char(c) = key {
+                match c {
+                    'x' => {
+                        self.screen = Screen::StartMenu {
+                            menu_state: MenuState::new(),
+                        }
+                    }
+                    ' ' => self.board_view.animations.skip(),
+                    _ => tutorial.handle_game_input(c),
+                }
+            },
+
+            // Moves in a networked game go through the session, which checks with the host
+            Screen::Online { session } => if let KeyCode::Char(c) = key {
+                match c {
+                    'x' => {
+                        self.screen = Screen::StartMenu {
+                            menu_state: MenuState::new(),
+                        }
+                    }
+                    ' ' => self.board_view.animations.skip(),
+                    _ => session.handle_game_input(c),
+                }
+            },
+        }
+    }
+
+    /// Offer `bot` as an opponent in Settings, and pick it to start with
+    ///
+    pub fn use_bot(&mut self, bot: Bot) {
+        self.bot = Some(bot);
+        self.settings.opponent = Opponent::Bot;
+    }
+
+    /// Is the chat line open? Every key goes to it while it is, even 'q'
+    ///
+    pub fn is_typing(&self) -> bool {
+        self.chat.is_open()
+    }
+
+    // Chat is for people playing each other: over the network or at the same keyboard
+    fn allows_chat(&self) -> bool {
+        match &self.screen {
+            Screen::InGame { game_state } => game_state.is_hot_seat(),
+            Screen::Online { session } => !session.is_spectating(),
+            _ => false,
+        }
+    }
+
+    // Send a chat message, or in hot-seat just log it under whoever has the keyboard
+    fn say(&mut self, message: String) {
+        match &mut self.screen {
+            Screen::Online { session } => session.send_chat(&message),
+            Screen::InGame { game_state } => {
+                let line = match game_state.active_seat() {
+                    Some(seat) => format!("{}: {message}", game_state.seats[seat].name),
+                    None => message,
+                };
+                self.chat.log.push(line);
+            }
+            _ => {}
         }
     }
 
     /// MenuEvent will contain one of the screens to switch to
     ///
     fn apply_menu_event(&mut self, menu_event: MenuEvent) {
-        let MenuEvent::Activate { menu_item } = menu_event;
+        let menu_item = match menu_event {
+            MenuEvent::Moved => {
+                self.audio.play(Cue::MenuMove);
+                return;
+            }
+            MenuEvent::Activate { menu_item } => menu_item,
+        };
+        self.audio.play(Cue::MenuSelect);
 
         match menu_item {
             MenuItem::StartGame => {
-                self.screen = Screen::InGame {
-                    game_state: Box::new(GameState::new()),
+                let game_state = match (self.settings.opponent, &self.bot) {
+                    (Opponent::Bot, Some(bot)) => GameState::against_bot(bot.clone()),
+                    _ => GameState::new(),
+                };
+                self.start_game(game_state);
+            }
+            MenuItem::HotSeat => self.start_game(GameState::hot_seat()),
+            MenuItem::Tutorial => {
+                self.screen = Screen::Tutorial {
+                    tutorial: Box::new(TutorialState::new()),
+                };
+            }
+            MenuItem::HowToPlay => {
+                self.screen = Screen::HowToPlay {
+                    how_to_play: HowToPlayState::new(),
+                };
+            }
+            MenuItem::Settings => {
+                self.screen = Screen::Settings {
+                    settings_state: SettingsState::new(),
                 };
             }
-            MenuItem::HowToPlay => {}
         }
     }
 
+    /// Terminal was resized: adopt the new size everywhere that depends on it
+    ///
+    pub fn resize(&mut self, config: Config) {
+        self.dirty = true;
+        self.board_view.resize(config.clone());
+        self.config = config;
+    }
+
     pub fn tick(&mut self, dt: Duration) {
-        match &mut self.screen {
+        let changed = match &mut self.screen {
             Screen::StartMenu { menu_state } => menu_state.tick(dt),
-            Screen::InGame { game_state } => game_state.update(),
+            Screen::HowToPlay { .. } | Screen::Settings { .. } => false,
+            Screen::InGame { game_state } => {
+                let updated = game_state.update();
+                self.board_view.animations.update(game_state, dt) || updated
+            }
+            Screen::Tutorial { tutorial } => {
+                let updated = tutorial.update();
+                self.board_view.animations.update(&tutorial.game_state, dt) || updated
+            }
+            Screen::Online { session } => {
+                let updated = session.update();
+                self.board_view.animations.update(&session.game_state, dt) || updated
+            }
+        };
+
+        self.dirty |= changed;
+        self.play_game_cues();
+    }
+
+    /// Switch straight to playing `game_state`, e.g. a game set up by a test
+    ///
+    pub fn start_game(&mut self, game_state: GameState) {
+        self.dirty = true;
+        self.chat = Chat::new();
+        self.screen = Screen::InGame {
+            game_state: Box::new(game_state),
+        };
+    }
+
+    /// Switch to a game hosted or joined over the network
+    ///
+    pub fn start_online(&mut self, session: NetSession) {
+        self.dirty = true;
+        self.chat = Chat::new();
+        self.screen = Screen::Online {
+            session: Box::new(session),
+        };
+    }
+
+    // Play sounds for whatever happened in the game since last time
+    fn play_game_cues(&mut self) {
+        let events = match &mut self.screen {
+            Screen::InGame { game_state } => game_state.take_events(),
+            Screen::Tutorial { tutorial } => tutorial.game_state.take_events(),
+            Screen::Online { session } => session.game_state.take_events(),
+            _ => return,
+        };
+
+        for cue in events.iter().filter_map(Cue::for_game_event) {
+            self.audio.play(cue);
+        }
+    }
+
+    /// Block until every sound has finished playing
+    ///
+    pub fn wait_for_audio(&self) {
+        self.audio.wait();
+    }
+
+    /// When the app next needs to tick without any input, e.g. for an animation frame
+    /// or the opponent finishing their think. None if it's only waiting on the player
+    ///
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let now = Instant::now();
+
+        match &self.screen {
+            Screen::StartMenu { menu_state } => Some(now + menu_state.time_to_next_frame()),
+            Screen::HowToPlay { .. } | Screen::Settings { .. } => None,
+            Screen::InGame { game_state } => self.game_deadline(game_state, now),
+            Screen::Tutorial { tutorial } => self.game_deadline(&tutorial.game_state, now),
+            // Also checking for messages from the other end
+            Screen::Online { session } => [
+                self.game_deadline(&session.game_state, now),
+                session.next_deadline(),
+            ]
+            .into_iter()
+            .flatten()
+            .min(),
         }
     }
 
+    // Whichever comes first of the next animation frame and the game moving on by itself
+    fn game_deadline(&self, game_state: &GameState, now: Instant) -> Option<Instant> {
+        let animation = self
+            .board_view
+            .animations
+            .is_animating()
+            .then(|| now + Duration::from_millis(ANIMATION_FRAME_MS));
+
+        [animation, game_state.next_deadline()]
+            .into_iter()
+            .flatten()
+            .min()
+    }
+
+    /// What the renderer reported for the last frame, shown in the debug overlay
+    ///
+    pub fn record_render(&mut self, stats: RenderStats) {
+        self.debug.record_render(stats);
+    }
+
+    /// Has anything changed since the app was last drawn?
+    ///
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
     pub fn draw(&mut self, frame: &mut Frame) {
+        self.dirty = false;
+        self.debug.record_frame(Instant::now());
+
+        self.draw_screen(frame);
+
+        // The debug overlay goes over everything, even the too small message
+        self.debug.draw(
+            self.screen.name(),
+            self.game_state(),
+            frame,
+            self.settings.theme,
+        );
+    }
+
+    /// Game being played on the current screen, if there is one
+    ///
+    pub fn game_state(&self) -> Option<&GameState> {
+        match &self.screen {
+            Screen::InGame { game_state } => Some(game_state),
+            Screen::Tutorial { tutorial } => Some(&tutorial.game_state),
+            Screen::Online { session } => Some(&session.game_state),
+            _ => None,
+        }
+    }
+
+    /// What the app is doing right now, for a crash report
+    ///
+    pub fn crash_context(&self) -> CrashContext {
+        let game_state = self.game_state();
+
+        CrashContext {
+            screen: self.screen.name(),
+            seed: game_state.and_then(GameState::deck_seed),
+            events: game_state
+                .map(|state| state.recent_events().map(ToString::to_string).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn draw_screen(&self, frame: &mut Frame) {
+        // Paint the theme's background before anything is drawn on top
+        clear_frame(frame, self.settings.theme.style(Role::Text));
+
+        // Screens assume a minimum size, so don't draw them at all below it
+        if self.config.is_too_small() {
+            self.draw_too_small(frame);
+            return;
+        }
+
         match &self.screen {
             Screen::StartMenu {
                 menu_state: _menu_state,
-            } => self.screen.draw(frame, &self.config),
-            Screen::InGame { game_state } => self.board_view.draw(game_state, frame),
+            } => self.screen.draw(frame, &self.config, &self.settings),
+            Screen::HowToPlay { .. } | Screen::Settings { .. } => {
+                self.screen.draw(frame, &self.config, &self.settings)
+            }
+            Screen::InGame { game_state } => {
+                self.board_view.draw(game_state, frame);
+                if game_state.is_hot_seat() {
+                    self.board_view
+                        .draw_chat(game_state, &self.chat.log, self.chat.input(), frame);
+                }
+            }
+            Screen::Tutorial { tutorial } => {
+                self.board_view.draw(&tutorial.game_state, frame);
+                self.board_view.draw_overlay(&tutorial.overlay_text(), frame);
+            }
+            Screen::Online { session } => {
+                self.board_view.draw(&session.game_state, frame);
+                self.board_view.draw_chat(
+                    &session.game_state,
+                    &session.chat,
+                    self.chat.input(),
+                    frame,
+                );
+                if let Some(text) = session.overlay_text() {
+                    self.board_view.draw_overlay(&text, frame);
+                }
+            }
+        }
+    }
+
+    /// Centered message asking for a bigger terminal, clipped to whatever space there is
+    ///
+    fn draw_too_small(&self, frame: &mut Frame) {
+        let (min_cols, min_rows) = Config::min_size();
+        let lines = [
+            "Terminal too small".to_string(),
+            format!("Need {}x{}", min_cols, min_rows),
+            format!("Have {}x{}", self.config.num_cols, self.config.num_rows),
+        ];
+
+        let style = self.settings.theme.style(Role::Warning);
+        let area = Rect::screen(&self.config).align(
+            self.config.num_cols,
+            lines.len(),
+            Align::Center,
+            Align::Center,
+        );
+
+        let mut canvas = Canvas::new(frame);
+        for (row, line) in lines.iter().enumerate() {
+            canvas.text_in(line, area.row(row), Align::Center, style);
         }
     }
 }
@@ -0,0 +1,216 @@
+// Sound effects.
+//
+// The app turns menu and game events into cues and plays them through an AudioBackend.
+// The real backend loads sounds from files named after their cue (e.g. "bust.wav")
+// in the assets directory. A missing file or audio device just means that cue stays silent.
+
+use std::{
+    cell::RefCell,
+    collections::HashSet,
+    env,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use rusty_audio::Audio;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::{
+    game::{GameEvent, RoundOutcome},
+    player::Player,
+};
+
+// File types rusty_audio can decode, tried in this order
+const SOUND_EXTENSIONS: [&str; 4] = ["wav", "ogg", "flac", "mp3"];
+
+/// Something worth playing a sound for
+///
+#[derive(EnumIter, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Cue {
+    Startup,
+    MenuMove,
+    MenuSelect,
+    CardDealt,
+    SideCardPlayed,
+    Stand,
+    Bust,
+    RoundWon,
+    RoundLost,
+    MatchWon,
+    MatchLost,
+}
+
+impl Cue {
+    /// Name of the cue's sound file, without an extension
+    ///
+    pub fn file_stem(self) -> &'static str {
+        match self {
+            Cue::Startup => "startup",
+            Cue::MenuMove => "menu_move",
+            Cue::MenuSelect => "menu_select",
+            Cue::CardDealt => "card_dealt",
+            Cue::SideCardPlayed => "side_card_played",
+            Cue::Stand => "stand",
+            Cue::Bust => "bust",
+            Cue::RoundWon => "round_won",
+            Cue::RoundLost => "round_lost",
+            Cue::MatchWon => "match_won",
+            Cue::MatchLost => "match_lost",
+        }
+    }
+
+    /// Cue for something that happened in a game.
+    /// Wins and losses are from the player's side of the table
+    ///
+    pub fn for_game_event(event: &GameEvent) -> Option<Cue> {
+        match event {
+            GameEvent::Dealt { .. } => Some(Cue::CardDealt),
+            GameEvent::Played { .. } => Some(Cue::SideCardPlayed),
+            GameEvent::Stood { .. } => Some(Cue::Stand),
+            GameEvent::Bust { .. } => Some(Cue::Bust),
+            GameEvent::RoundEnded { outcome } => match outcome {
+                RoundOutcome::PlayerWon => Some(Cue::RoundWon),
+                RoundOutcome::OpponentWon => Some(Cue::RoundLost),
+                RoundOutcome::Tied => None,
+            },
+            GameEvent::GameOver { winner } => match winner {
+                Player::Player => Some(Cue::MatchWon),
+                Player::Opponent => Some(Cue::MatchLost),
+            },
+            GameEvent::NewRound | GameEvent::NewGame => None,
+        }
+    }
+}
+
+/// Directory sounds are loaded from: $KAAZAP_ASSETS if set, otherwise "assets"
+///
+pub fn assets_dir() -> PathBuf {
+    env::var_os("KAAZAP_ASSETS").map_or_else(|| PathBuf::from("assets"), PathBuf::from)
+}
+
+/// Somewhere to play cues
+///
+pub trait AudioBackend {
+    fn play(&mut self, cue: Cue);
+
+    /// Block until every sound has finished, so quitting doesn't cut one off
+    fn wait(&self) {}
+}
+
+/// Plays cues through the default audio device with rusty_audio
+///
+pub struct RustyAudioBackend {
+    audio: Option<Audio>, // None if the device couldn't be opened
+    loaded: HashSet<Cue>,
+}
+
+impl RustyAudioBackend {
+    /// Open the audio device and load every cue found in `dir/sounds`.
+    /// rusty_audio panics on a file it can't decode, which is caught and leaves that cue silent
+    ///
+    pub fn load(dir: &Path) -> Self {
+        let Some(mut audio) = quietly(Audio::new).filter(|audio| !audio.disabled()) else {
+            return Self {
+                audio: None,
+                loaded: HashSet::new(),
+            };
+        };
+
+        let sounds = dir.join("sounds");
+        let mut loaded = HashSet::new();
+
+        for cue in Cue::iter() {
+            // rusty_audio panics rather than erroring on a missing file, so look first
+            let Some(path) = SOUND_EXTENSIONS
+                .iter()
+                .map(|extension| sounds.join(cue.file_stem()).with_extension(extension))
+                .find(|path| path.is_file())
+            else {
+                continue;
+            };
+
+            if quietly(|| audio.add(cue.file_stem(), &path)).is_some() {
+                loaded.insert(cue);
+            }
+        }
+
+        Self {
+            audio: Some(audio),
+            loaded,
+        }
+    }
+}
+
+impl AudioBackend for RustyAudioBackend {
+    // Cues with no sound loaded are skipped, as rusty_audio panics on unknown names
+    fn play(&mut self, cue: Cue) {
+        if let Some(audio) = &mut self.audio
+            && self.loaded.contains(&cue)
+        {
+            audio.play(cue.file_stem());
+        }
+    }
+
+    fn wait(&self) {
+        if let Some(audio) = &self.audio {
+            audio.wait();
+        }
+    }
+}
+
+/// Plays nothing, for `--no-sound`
+///
+#[derive(Debug, Default)]
+pub struct SilentBackend;
+
+impl AudioBackend for SilentBackend {
+    fn play(&mut self, _cue: Cue) {}
+}
+
+/// Plays nothing but remembers every cue, so tests can check what would have played.
+/// Clones share the same record, so keep one to look at after handing the other to the app
+///
+#[derive(Debug, Default, Clone)]
+pub struct RecordingBackend {
+    played: Rc<RefCell<Vec<Cue>>>,
+}
+
+impl RecordingBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every cue played so far, oldest first
+    ///
+    pub fn played(&self) -> Vec<Cue> {
+        self.played.borrow().clone()
+    }
+
+    /// How many times `cue` was played
+    ///
+    pub fn count(&self, cue: Cue) -> usize {
+        self.played
+            .borrow()
+            .iter()
+            .filter(|&&played| played == cue)
+            .count()
+    }
+}
+
+impl AudioBackend for RecordingBackend {
+    fn play(&mut self, cue: Cue) {
+        self.played.borrow_mut().push(cue);
+    }
+}
+
+// Run `f`, returning None instead of panicking and without printing the panic
+fn quietly<T>(f: impl FnOnce() -> T) -> Option<T> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(f)).ok();
+    panic::set_hook(hook);
+
+    result
+}
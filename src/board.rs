@@ -1,174 +1,682 @@
-use std::cmp::max;
+use std::cmp::{max, min};
 
 use crate::{
-    CARD_HEIGHT, CARD_WIDTH, H_PAD,
-    card::CardView,
+    CHAT_LINES, DEAL_ANIMATION_TIME_MS, FLIP_ANIMATION_TIME_MS, H_PAD, HAND_SIZE, STAND_THRESHOLD,
+    TARGET_SCORE,
+    animation::{Animations, Motion},
+    canvas::{BoxGlyphs, Canvas, text_width},
+    card::{CardKind, CardSize, CardStyle, CardView},
+    chat,
     config::Config,
-    frame::{Drawable, Frame},
-    game::{GamePhase, GameState, RoundOutcome}, player::Player,
+    frame::{Cell, Drawable, Frame},
+    game::{GamePhase, GameState, RoundOutcome},
+    how_to_play::wrap_text,
+    layout::{Align, Rect, Size},
+    player::{Player, PlayerState},
+    theme::{Role, Theme},
 };
 
-pub struct PlayArea {
-    pub left: usize,
-    pub right: usize,
+/// Strategy for fitting the board into the terminal.
+/// Picked from the space available and swapped whenever the terminal is resized.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BoardLayout {
+    Full,    // full size cards
+    Compact, // small 5x3 cards
+    Tokens,  // single line cards like "[+3]", for split panes
 }
 
-pub struct BoardView {
-    pub config: Config,
-    player_area: PlayArea,
-    opponent_area: PlayArea,
-    cards_per_row: usize,
+impl BoardLayout {
+    /// The roomiest layout that fits, falling back to tokens
+    ///
+    pub fn choose(config: &Config) -> Self {
+        [BoardLayout::Full, BoardLayout::Compact]
+            .into_iter()
+            .find(|layout| layout.fits(config))
+            .unwrap_or(BoardLayout::Tokens)
+    }
+
+    pub fn fits(&self, config: &Config) -> bool {
+        let (min_cols, min_rows) = self.min_size();
+
+        config.num_cols >= min_cols && config.num_rows >= min_rows
+    }
+
+    /// Smallest (cols, rows) this layout can be drawn in:
+    /// a full hand must fit in each half of the screen either side of the divider,
+    /// and every row must fit with room for at least one row of dealer cards
+    ///
+    pub fn min_size(&self) -> (usize, usize) {
+        let card = self.card_size();
+        let row_width = HAND_SIZE * (card.width() + 1) - 1;
+
+        let cols = 2 * (row_width + 2 * self.padding()) + 1;
+        let rows = self
+            .row_sizes()
+            .iter()
+            .map(|size| match size {
+                Size::Fixed(n) => *n,
+                Size::Fill => card.height(),
+            })
+            .sum();
+
+        (cols, rows)
+    }
+
+    pub fn card_size(&self) -> CardSize {
+        match self {
+            BoardLayout::Full => CardSize::Full,
+            BoardLayout::Compact => CardSize::Small,
+            BoardLayout::Tokens => CardSize::Token,
+        }
+    }
+
+    // Horizontal padding on either side of each half of the board
+    fn padding(&self) -> usize {
+        match self {
+            BoardLayout::Full => H_PAD,
+            BoardLayout::Compact => 2,
+            BoardLayout::Tokens => 1,
+        }
+    }
+
+    // Rows of the board from top to bottom, see BoardRegions::new
+    fn row_sizes(&self) -> [Size; 11] {
+        let card_height = self.card_size().height();
+        let header_gap = match self {
+            BoardLayout::Full | BoardLayout::Compact => 1,
+            BoardLayout::Tokens => 0,
+        };
+
+        [
+            Size::Fixed(1),
+            Size::Fixed(2), // header
+            Size::Fixed(header_gap),
+            Size::Fill, // dealer cards
+            Size::Fixed(1),
+            Size::Fixed(1), // log
+            Size::Fixed(1),
+            Size::Fixed(card_height), // played cards
+            Size::Fixed(1),
+            Size::Fixed(card_height), // hand
+            Size::Fixed(1),           // status line
+        ]
+    }
 }
 
-impl BoardView {
-    pub fn new(config: Config) -> Self {
-        let player_area = PlayArea {
-            left: H_PAD,
-            right: config.num_cols / 2 - H_PAD,
+/// Where each part of one side of the board is drawn
+///
+pub struct SideRegions {
+    pub header: Rect,
+    pub dealer: Rect,
+    pub played: Rect,
+    pub hand: Rect,
+    pub status: Rect,
+}
+
+/// Rectangles for every part of the board, computed from the layout
+///
+pub struct BoardRegions {
+    pub player: SideRegions,
+    pub opponent: SideRegions,
+    pub divider: [Rect; 2], // the middle column above and below the log
+    pub log: Rect,          // round and game outcome messages, across both sides
+    pub chat: Rect,         // recent chat, across both sides below the dealer cards
+}
+
+impl BoardRegions {
+    pub fn new(config: &Config, layout: BoardLayout) -> Self {
+        let rows = Rect::screen(config).split_rows(&layout.row_sizes());
+        let (header, dealer, log, played, hand, status) =
+            (rows[1], rows[3], rows[5], rows[7], rows[9], rows[10]);
+
+        // Chat takes the bottom of the dealer rows, as much as it can while leaving a gap
+        // under a row of dealer cards
+        let spare = dealer
+            .height
+            .saturating_sub(layout.card_size().height() + 1);
+        let chat_height = min(CHAT_LINES, spare);
+        let (dealer, chat) = if chat_height == 0 {
+            (
+                dealer,
+                Rect::new(dealer.x, dealer.bottom(), dealer.width, 0),
+            )
+        } else {
+            let split = dealer.split_rows(&[Size::Fill, Size::Fixed(1), Size::Fixed(chat_height)]);
+            (split[0], split[2])
         };
 
-        let opponent_area = PlayArea {
-            left: config.num_cols / 2 + H_PAD,
-            right: config.num_cols - H_PAD,
+        let columns = Rect::screen(config).split_columns(&[Size::Fill, Size::Fixed(1), Size::Fill]);
+
+        // The divider stops either side of the log, so its text never runs over it
+        let middle =
+            columns[1].split_rows(&[Size::Fixed(log.y), Size::Fixed(log.height), Size::Fill]);
+        let divider = [middle[0], middle[2]];
+
+        // Each side is its half of the screen in every row, padded away from the edges
+        let side = |half: Rect| {
+            let within = |row: Rect| Rect::new(half.x, row.y, half.width, row.height);
+            let padded = |row: Rect| within(row).pad(layout.padding(), 0);
+
+            SideRegions {
+                header: padded(header),
+                dealer: padded(dealer),
+                played: padded(played),
+                hand: padded(hand),
+                status: padded(status),
+            }
         };
 
-        let available_width = player_area.right - player_area.left;
-        let slot_width = CARD_WIDTH + 1;
-        let cards_per_row = max(1, available_width / slot_width);
+        Self {
+            player: side(columns[0]),
+            opponent: side(columns[2]),
+            divider,
+            log,
+            chat,
+        }
+    }
+
+    pub fn side(&self, player: Player) -> &SideRegions {
+        match player {
+            Player::Player => &self.player,
+            Player::Opponent => &self.opponent,
+        }
+    }
+}
+
+pub struct BoardView {
+    pub config: Config,
+    layout: BoardLayout,
+    regions: BoardRegions,
+    pub theme: Theme,
+    pub card_style: CardStyle,
+    pub animations: Animations,
+}
+
+impl BoardView {
+    pub fn new(config: Config) -> Self {
+        let layout = BoardLayout::choose(&config);
+        let regions = BoardRegions::new(&config, layout);
 
         Self {
             config,
-            player_area,
-            opponent_area,
-            cards_per_row,
+            layout,
+            regions,
+            theme: Theme::default(),
+            card_style: CardStyle::default(),
+            animations: Animations::new(),
         }
     }
 
+    // Pick a layout for the new terminal size and recompute the regions
+    //
+    pub fn resize(&mut self, config: Config) {
+        let layout = BoardLayout::choose(&config);
+
+        self.regions = BoardRegions::new(&config, layout);
+        self.config = config;
+        self.layout = layout;
+    }
+
+    pub fn layout(&self) -> BoardLayout {
+        self.layout
+    }
+
+    pub fn regions(&self) -> &BoardRegions {
+        &self.regions
+    }
+
     // Draw Text Helper
     //
-    fn draw_text(&self, text: &str, x: usize, y: usize, frame: &mut Frame) {
-        for (i, ch) in text.chars().enumerate() {
-            frame[x + i][y] = ch;
+    // Draws on the first line of `area`, aligned within it.
+    // Text is clipped to the area so it never runs into its neighbours
+    //
+    fn draw_text(&self, text: &str, area: Rect, align: Align, role: Role, frame: &mut Frame) {
+        Canvas::new(frame).text_in(text, area, align, self.theme.style(role));
+    }
+
+    // Scores are flagged once they're close to busting
+    //
+    fn score_role(score: i32) -> Role {
+        if score > TARGET_SCORE {
+            Role::Danger
+        } else if score > STAND_THRESHOLD as i32 {
+            Role::Warning
+        } else {
+            Role::Text
         }
     }
 
-    // Draw round/game outcome text in the middle of screen
+    // Draw round/game outcome text in the log between the dealer and played rows
     //
     fn draw_round_outcome_text(&self, state: &GameState, frame: &mut Frame) {
-        let mid_x = self.config.num_cols / 2;
-        let mid_y = self.config.num_rows / 2;
+        if let Some(text) = Self::round_outcome_text(state) {
+            self.draw_text(&text, self.regions.log, Align::Center, Role::Text, frame);
+        }
+    }
 
-        if let GamePhase::GameOver { winner } = state.game_phase {
-            match winner {
-                Player::Player => {
-                    self.draw_text("YOU WIN THE GAME! :)", mid_x - 9, mid_y, frame);
-                }
-                Player::Opponent => {
-                    self.draw_text("YOU LOST THE GAME! :(", mid_x - 9, mid_y, frame);
-                }
+    fn round_outcome_text(state: &GameState) -> Option<String> {
+        if Self::names_seats(state) {
+            Self::named_outcome_text(state)
+        } else {
+            Self::outcome_text(state)
+        }
+    }
+
+    /// Draw the newest chat `messages`, as many as fit in the chat area and newest at the
+    /// bottom, and the chat line across the status line while it's being typed.
+    /// A board with no room for a chat area shows the newest in the log while it's free
+    ///
+    pub fn draw_chat(
+        &self,
+        state: &GameState,
+        messages: &[String],
+        input: Option<&str>,
+        frame: &mut Frame,
+    ) {
+        let area = self.regions.chat;
+        if area.height > 0 {
+            let shown = &messages[messages.len().saturating_sub(area.height)..];
+            let top = area.bottom() - shown.len();
+            for (i, message) in shown.iter().enumerate() {
+                let line = Rect::new(area.x, top + i, area.width, 1);
+                self.draw_text(message, line, Align::Center, Role::Text, frame);
             }
+        } else if let Some(latest) = messages.last()
+            && Self::round_outcome_text(state).is_none()
+        {
+            self.draw_text(latest, self.regions.log, Align::Center, Role::Text, frame);
+        }
 
+        let Some(input) = input else {
             return;
+        };
+
+        // Covers both sides' status, the turn doesn't matter while typing
+        let (left, right) = (self.regions.player.status, self.regions.opponent.status);
+        let line = Rect::new(left.x, left.y, right.right() - left.x, 1);
+        Canvas::new(frame).fill(line, Cell::new(' ', self.theme.style(Role::Text)));
+
+        // The hint goes first so what's typed stays on top where they overlap
+        if input.is_empty() {
+            self.draw_text(&chat::emote_hint(), line, Align::End, Role::Text, frame);
         }
+        let prompt = format!("Say: {input}_");
+        self.draw_text(&prompt, line, Align::Start, Role::Title, frame);
+    }
 
-        match state.round_outcome {
-            Some(RoundOutcome::PlayerWon) => {
-                self.draw_text("You won this round!", mid_x - 9, mid_y, frame);
-            }
-            Some(RoundOutcome::Tied) => {
-                self.draw_text("You Tied!", mid_x - 4, mid_y, frame);
+    fn outcome_text(state: &GameState) -> Option<String> {
+        let text = if let GamePhase::GameOver { winner } = state.game_phase {
+            match winner {
+                Player::Player => "YOU WIN THE GAME! :)",
+                Player::Opponent => "YOU LOST THE GAME! :(",
             }
-            Some(RoundOutcome::OpponentWon) => {
-                self.draw_text("Opponent won the round!", mid_x - 11, mid_y, frame);
+        } else {
+            match state.round_outcome? {
+                RoundOutcome::PlayerWon => "You won this round!",
+                RoundOutcome::Tied => "You Tied!",
+                RoundOutcome::OpponentWon => "Opponent won the round!",
             }
-            None => {}
+        };
+
+        Some(text.to_string())
+    }
+
+    // Both players are "you" in hot-seat and neither is to a spectator, so name the winner
+    //
+    fn named_outcome_text(state: &GameState) -> Option<String> {
+        if let GamePhase::GameOver { winner } = state.game_phase {
+            return Some(format!("{} WINS THE GAME!", state.seats[winner].name));
         }
+
+        let text = match state.round_outcome? {
+            RoundOutcome::PlayerWon => {
+                format!("{} won the round!", state.seats[Player::Player].name)
+            }
+            RoundOutcome::OpponentWon => {
+                format!("{} won the round!", state.seats[Player::Opponent].name)
+            }
+            RoundOutcome::Tied => "Tied!".to_string(),
+        };
+
+        Some(text)
     }
 
-    // Draw whose turn it is
+    // Hot-seat players and spectators aren't one side or the other, so seats go by name
+    //
+    fn names_seats(state: &GameState) -> bool {
+        state.is_hot_seat() || state.is_spectated()
+    }
+
+    // Draw whose turn it is on the status line at the bottom of the screen
     //
     fn draw_turn_text(&self, state: &GameState, frame: &mut Frame) {
-        let mid = self.config.num_cols / 2;
-        let padding_y: usize = 4;
-        let padding_x: usize = 15;
-
-        match state.game_phase {
-            GamePhase::PlayerTurn => self.draw_text(
-                "Your Turn",
-                mid - padding_x,
-                self.config.num_rows - padding_y,
-                frame,
-            ),
-            GamePhase::OpponentThinking { until: _until } => self.draw_text(
-                "Opponent's Turn",
-                self.config.num_cols - padding_x - 4,
-                self.config.num_rows - padding_y,
-                frame,
-            ),
-            _ => {}
-        }
+        let player = match state.game_phase {
+            GamePhase::Turn { seat } | GamePhase::Thinking { seat, .. } => seat,
+            _ => return,
+        };
+
+        // Only someone playing alone at this keyboard is "you"
+        let text = if state.controllers[player].is_human() && !state.is_hot_seat() {
+            "Your Turn".to_string()
+        } else {
+            format!("{}'s Turn", state.seats[player].name)
+        };
+
+        let status = self.regions.side(player).status;
+        self.draw_text(&text, status, Align::Start, Role::Text, frame);
     }
 
     // Draw Top info (Player name, score, et)
     //
     fn draw_top_info(&self, state: &GameState, frame: &mut Frame) {
-        let mid = self.config.num_cols / 2;
-        let padding_y: usize = 1;
-        let padding_x: usize = 4;
+        // Names already say which seat is which when neither is "you"
+        let (player_label, opponent_label) = if Self::names_seats(state) {
+            (None, None)
+        } else {
+            (Some("Player"), Some("Opponent"))
+        };
 
-        // --- Player Side ---
-        let player_name_display = format!("Player: {}", state.player.name);
-        self.draw_text(player_name_display.as_str(), padding_x, padding_y, frame);
+        self.draw_side_info(
+            &state.seats[Player::Player],
+            player_label,
+            &self.regions.player,
+            frame,
+        );
+        self.draw_side_info(
+            &state.seats[Player::Opponent],
+            opponent_label,
+            &self.regions.opponent,
+            frame,
+        );
+    }
 
-        let player_score_display = format!("Score: {}", state.player.score());
-        self.draw_text(player_score_display.as_str(), mid - 12, padding_y, frame);
+    // Name and score on the first line, bust/stood status and rounds won on the second.
+    // Smaller layouts drop the labels so everything fits in half the screen.
+    //
+    fn draw_side_info(
+        &self,
+        side: &PlayerState,
+        label: Option<&str>,
+        regions: &SideRegions,
+        frame: &mut Frame,
+    ) {
+        let verbose = self.layout == BoardLayout::Full;
+
+        let name_display = match label {
+            Some(label) if verbose => format!("{}: {}", label, side.name),
+            _ => side.name.clone(),
+        };
+        let score_display = format!("Score: {}", side.score());
+        let round_score_display = if verbose {
+            format!("Rounds won: {}", side.rounds_won)
+        } else {
+            format!("Won: {}", side.rounds_won)
+        };
+
+        // Left hand text gets whatever the right aligned text leaves over
+        let line = |offset: usize, right_text: &str| {
+            let columns = regions.header.row(offset).split_columns(&[
+                Size::Fill,
+                Size::Fixed(1),
+                Size::Fixed(text_width(right_text)),
+            ]);
+            (columns[0], columns[2])
+        };
 
-        let player_round_score_display = format!("Rounds won: {}", state.player.rounds_won);
+        let (name_area, score_area) = line(0, &score_display);
+        self.draw_text(&name_display, name_area, Align::Start, Role::Text, frame);
+        let score_role = Self::score_role(side.score());
+        self.draw_text(&score_display, score_area, Align::End, score_role, frame);
+
+        let (status_area, rounds_area) = line(1, &round_score_display);
         self.draw_text(
-            player_round_score_display.as_str(),
-            mid - 17,
-            padding_y + 1,
+            &round_score_display,
+            rounds_area,
+            Align::End,
+            Role::Text,
             frame,
         );
 
         // If Bust or stood, display so!
-        //
-        if state.player.bust {
-            self.draw_text("BUSTED!!", padding_x, padding_y + 1, frame);
-        } else if state.player.stood {
-            self.draw_text("Stood", padding_x, padding_y + 1, frame);
+        if side.bust {
+            self.draw_text("BUSTED!!", status_area, Align::Start, Role::Danger, frame);
+        } else if side.stood {
+            self.draw_text("Stood", status_area, Align::Start, Role::Text, frame);
         }
+    }
 
-        // --- Opponent Side ---
-        let opponent_name_display = format!("Opponent: {}", state.opponent.name);
-        self.draw_text(
-            opponent_name_display.as_str(),
-            mid + padding_x,
-            padding_y,
-            frame,
-        );
+    // Draw a boxed message panel on top of the board, just below the round outcome text.
+    // Used by screens that layer extra information over a game (e.g. the tutorial).
+    //
+    pub fn draw_overlay(&self, paragraphs: &[String], frame: &mut Frame) {
+        let log = self.regions.log;
+        let below_log =
+            Rect::screen(&self.config).split_rows(&[Size::Fixed(log.bottom()), Size::Fill])[1];
 
-        let opponent_score_display = format!("Score: {}", state.opponent.score());
-        self.draw_text(
-            opponent_score_display.as_str(),
-            self.config.num_cols - 12,
-            padding_y,
-            frame,
-        );
+        self.draw_panel(paragraphs, below_log, Align::Start, frame);
+    }
 
-        let opponent_round_score_display = format!("Rounds won: {}", state.opponent.rounds_won);
-        self.draw_text(
-            opponent_round_score_display.as_str(),
-            self.config.num_cols - 17,
-            padding_y + 1,
-            frame,
-        );
-        //
-        // If Bust or stood, display so!
-        if state.opponent.bust {
-            self.draw_text("BUSTED!!", mid + padding_x, padding_y + 1, frame);
-        } else if state.opponent.stood {
-            self.draw_text("Stood", mid + padding_x, padding_y + 1, frame);
+    // The hot-seat privacy screen blanks out the hands, which are face down while it's up
+    // anyway, so the played cards and the log stay in view above it
+    //
+    fn draw_handover(&self, paragraphs: &[String], frame: &mut Frame) {
+        let played = self.regions.player.played;
+        let below_played =
+            Rect::screen(&self.config).split_rows(&[Size::Fixed(played.bottom()), Size::Fill])[1];
+
+        Canvas::new(frame).fill(below_played, Cell::new(' ', self.theme.style(Role::Text)));
+        self.draw_panel(paragraphs, below_played, Align::Center, frame);
+    }
+
+    // Boxed, wrapped text, centered across `area` and clipped to it
+    //
+    fn draw_panel(&self, paragraphs: &[String], area: Rect, v_align: Align, frame: &mut Frame) {
+        let area = area.pad(H_PAD, 0);
+
+        let box_width = area.width.min(64);
+        let text_width = box_width.saturating_sub(4).max(1);
+
+        let lines: Vec<String> = paragraphs
+            .iter()
+            .flat_map(|paragraph| wrap_text(paragraph, text_width))
+            .collect();
+
+        let panel = area.align(box_width, lines.len() + 2, Align::Center, v_align);
+
+        // clear the panel and draw its border
+        Canvas::new(frame).panel(panel, BoxGlyphs::ASCII, self.theme.style(Role::Text));
+
+        let text_area = panel.pad(2, 1);
+        for (i, line) in lines.iter().enumerate().take(text_area.height) {
+            self.draw_text(line, text_area.row(i), Align::Start, Role::Text, frame);
+        }
+    }
+
+    // Draw a single card at the current layout's size
+    //
+    fn draw_card(
+        &self,
+        x: usize,
+        y: usize,
+        text: String,
+        kind: Option<CardKind>,
+        frame: &mut Frame,
+    ) {
+        self.draw_sized_card(x, y, self.layout.card_size(), text, kind, frame);
+    }
+
+    fn draw_sized_card(
+        &self,
+        x: usize,
+        y: usize,
+        size: CardSize,
+        text: String,
+        kind: Option<CardKind>,
+        frame: &mut Frame,
+    ) {
+        CardView {
+            x,
+            y,
+            text,
+            kind,
+            style: self.card_style,
+            size,
+        }
+        .draw(frame, self.theme);
+    }
+
+    // Draw a card part way through turning over, `progress` going from 0.0 to 1.0.
+    // The back narrows to an edge, then the face widens back out from it.
+    //
+    fn draw_flipping_card(
+        &self,
+        slot: Rect,
+        progress: f32,
+        text: String,
+        kind: Option<CardKind>,
+        frame: &mut Frame,
+    ) {
+        let face_up = progress >= 0.5;
+
+        // tokens are a single line, so just swap the back for the face halfway through
+        if self.layout.card_size() == CardSize::Token {
+            let (text, kind) = if face_up {
+                (text, kind)
+            } else {
+                ("?".to_string(), None)
+            };
+            self.draw_card(slot.x, slot.y, text, kind, frame);
+            return;
+        }
+
+        let role = if face_up {
+            kind.map_or(Role::CardBorder, |kind| kind.role())
+        } else {
+            Role::CardBorder
+        };
+        let style = self.theme.style(role);
+
+        let turned = (1.0 - 2.0 * progress).abs();
+        let width = ((slot.width as f32 * turned).round() as usize).max(1);
+        let area = slot.align(width, slot.height, Align::Center, Align::Start);
+
+        let mut canvas = Canvas::new(frame);
+        let glyphs = self.card_style.glyphs();
+
+        // too narrow for a box, so only the card's edge shows
+        if area.width < 3 {
+            canvas.fill(area, Cell::new(glyphs.vertical, style));
+            return;
+        }
+
+        canvas.panel(area, glyphs, style);
+        if face_up {
+            let text_row = area.pad(1, 0).row(slot.height / 2);
+            canvas.text_in(&text, text_row, Align::Center, style.bold());
+        }
+    }
+
+    // Where the deck sits, cards are dealt from here
+    //
+    fn deck_position(&self) -> (usize, usize) {
+        let card = self.layout.card_size();
+        let x = self.regions.divider[0].x.saturating_sub(card.width() / 2);
+
+        (x, self.regions.player.dealer.y)
+    }
+
+    // Where the index'th card in a row of cards goes, wrapping onto extra rows
+    //
+    fn slot(&self, area: Rect, index: usize) -> Rect {
+        Self::sized_slot(area, index, self.layout.card_size())
+    }
+
+    // The same for cards of size `card`, as many side by side as fit in `area`
+    //
+    fn sized_slot(area: Rect, index: usize, card: CardSize) -> Rect {
+        let per_row = max(1, (area.width + 1) / (card.width() + 1));
+        let row = index / per_row;
+        let col = index % per_row;
+
+        Rect::new(
+            area.x + col * (card.width() + 1),
+            area.y + row * (card.height() + 1),
+            card.width(),
+            card.height(),
+        )
+    }
+
+    // Dealer cards at the layout's size while their rows fit in `area`,
+    // shrinking to tokens once they'd wrap past its bottom
+    //
+    fn dealer_card_size(&self, area: Rect, count: usize) -> CardSize {
+        let size = self.layout.card_size();
+        let fits = count == 0 || Self::sized_slot(area, count - 1, size).bottom() <= area.bottom();
+
+        if fits { size } else { CardSize::Token }
+    }
+
+    // Dealer cards wrap onto extra rows when a row fills up, staying inside the dealer area.
+    // Newly dealt cards slide in from the deck.
+    //
+    fn draw_dealer_row(&self, side: &PlayerState, owner: Player, frame: &mut Frame) {
+        let area = self.regions.side(owner).dealer;
+        let size = self.dealer_card_size(area, side.dealer_row.len());
+
+        for (i, c) in side.dealer_row.iter().enumerate() {
+            let slot = Self::sized_slot(area, i, size);
+            // more cards than even tokens have room for
+            if slot.bottom() > area.bottom() {
+                break;
+            }
+
+            let (x, y) = match self.animations.dealer(owner, i) {
+                Some(animation) => {
+                    lerp(self.deck_position(), (slot.x, slot.y), animation.progress())
+                }
+                None => (slot.x, slot.y),
+            };
+
+            let text = c.value.to_string();
+            self.draw_sized_card(x, y, size, text, Some(CardKind::Dealer), frame);
+        }
+    }
+
+    // Side cards played this round. Newly played cards turn face up,
+    // those from a hidden hand first travel up out of it.
+    //
+    fn draw_played_row(&self, side: &PlayerState, owner: Player, frame: &mut Frame) {
+        let regions = self.regions.side(owner);
+
+        for (i, c) in side.played_row.iter().enumerate() {
+            let slot = self.slot(regions.played, i);
+            let text = format!("{:+}", c.value);
+            let kind = Some(CardKind::of_side_card(c));
+
+            let Some(animation) = self.animations.played(owner, i) else {
+                self.draw_card(slot.x, slot.y, text, kind, frame);
+                continue;
+            };
+
+            match animation.motion {
+                Motion::Reveal { hand_index } => {
+                    // first move, still hidden, then turn over in the slot
+                    let split = DEAL_ANIMATION_TIME_MS as f32
+                        / (DEAL_ANIMATION_TIME_MS + FLIP_ANIMATION_TIME_MS) as f32;
+                    let progress = animation.progress();
+
+                    if progress < split {
+                        let from = self.slot(regions.hand, hand_index);
+                        let (x, y) = lerp((from.x, from.y), (slot.x, slot.y), progress / split);
+                        self.draw_card(x, y, "?".to_string(), None, frame);
+                    } else {
+                        let flip = (progress - split) / (1.0 - split);
+                        self.draw_flipping_card(slot, flip, text, kind, frame);
+                    }
+                }
+                _ => self.draw_flipping_card(slot, animation.progress(), text, kind, frame),
+            }
         }
     }
 
@@ -178,111 +686,83 @@ impl BoardView {
     pub fn draw(&self, state: &GameState, frame: &mut Frame) {
         //
         // draw a vertical divider down the middle
-        let mid = self.config.num_cols / 2;
-        for y in 0..self.config.num_rows {
-            if mid < frame.len() && y < frame[0].len() {
-                frame[mid][y] = '|';
-            }
+        let divider = Cell::new('|', self.theme.style(Role::Divider));
+        for part in self.regions.divider {
+            Canvas::new(frame).fill(part, divider);
         }
 
         // Top Info
         self.draw_top_info(state, frame);
 
-        // layout constants (simple, tweak later)
-        let dealer_y: usize = 4;
-        let hand_y = self.config.num_rows.saturating_sub(CARD_HEIGHT + 1);
-        let played_y = hand_y - CARD_HEIGHT - 1;
-
-        let spacing_x = CARD_WIDTH + 1;
-
-        let player_origin_x = self.player_area.left;
-        let opp_origin_x = self.opponent_area.left;
+        // Hands go first so cards moving out of them are drawn on top
 
         // --- Player side ---
         //
+        // Hand cards
+        self.draw_hand(state, Player::Player, frame);
         // Dealer Cards
-        for (i, c) in state.player.dealer_row.iter().enumerate() {
-            let row = i / self.cards_per_row;
-            let col = i % self.cards_per_row;
-
-            let x = player_origin_x + col * spacing_x;
-            let y = dealer_y + row * (CARD_HEIGHT + 1);
-
-            CardView {
-                x,
-                y,
-                text: c.value.to_string(),
-            }
-            .draw(frame);
-        }
+        self.draw_dealer_row(&state.seats[Player::Player], Player::Player, frame);
         // Played Cards
-        for (i, c) in state.player.played_row.iter().enumerate() {
-            let x = player_origin_x + i * spacing_x;
-            CardView {
-                x,
-                y: played_y,
-                text: c.value.to_string(),
-            }
-            .draw(frame);
-        }
-        // Hand cards
-        for (i, c) in state.player.hand.iter().enumerate() {
-            if c.is_some() {
-                let x = player_origin_x + i * spacing_x;
-                CardView {
-                    x,
-                    y: hand_y,
-                    text: c.unwrap().value.to_string(),
-                }
-                .draw(frame);
-            }
-        }
+        self.draw_played_row(&state.seats[Player::Player], Player::Player, frame);
 
         // --- Opponent side ---
         //
+        // Hand cards, hidden from the player
+        self.draw_hand(state, Player::Opponent, frame);
         // Dealer Cards
-        for (i, c) in state.opponent.dealer_row.iter().enumerate() {
-            let row = i / self.cards_per_row;
-            let col = i % self.cards_per_row;
-
-            let x = opp_origin_x + col * spacing_x;
-            let y = dealer_y + row * (CARD_HEIGHT + 1);
-            CardView {
-                x,
-                y,
-                text: c.value.to_string(),
-            }
-            .draw(frame);
-        }
+        self.draw_dealer_row(&state.seats[Player::Opponent], Player::Opponent, frame);
         // Played Cards
-        for (i, c) in state.opponent.played_row.iter().enumerate() {
-            if c.value != 0 {
-                let x = opp_origin_x + i * spacing_x;
-                CardView {
-                    x,
-                    y: played_y,
-                    text: c.value.to_string(),
-                }
-                .draw(frame);
-            }
-        }
-        // Opponent hand cards (hidden values)
-        for (i, c) in state.opponent.hand.iter().enumerate() {
-            if c.is_some() {
-                let x = opp_origin_x + i * spacing_x;
-                CardView {
-                    x,
-                    y: hand_y,
-                    text: "?".to_string(),
-                }
-                .draw(frame);
-            }
-        }
+        self.draw_played_row(&state.seats[Player::Opponent], Player::Opponent, frame);
 
         // Draw Turn Text
         self.draw_turn_text(state, frame);
 
         // Draw Round/Game Outcome if it exists
         self.draw_round_outcome_text(state, frame);
+
+        // Hot-seat privacy screen while the keyboard changes hands
+        if let GamePhase::Handover { to } = state.game_phase {
+            let name = &state.seats[to].name;
+            self.draw_handover(
+                &[
+                    format!("Pass the keyboard to {name}."),
+                    format!("{name}, press 'c' when nobody else can see the screen."),
+                ],
+                frame,
+            );
+        }
     }
+
+    // Hand cards face up with their values, or face down as "?" when
+    // whoever's at the keyboard mustn't see them
+    //
+    fn draw_hand(&self, state: &GameState, seat: Player, frame: &mut Frame) {
+        let spacing_x = self.layout.card_size().width() + 1;
+        let area = self.regions.side(seat).hand;
+        let side = &state.seats[seat];
+        let face_up = state.shows_hand(seat);
+
+        for (i, card) in side.hand.iter().enumerate() {
+            let Some(card) = card else {
+                continue;
+            };
+
+            let x = area.x + i * spacing_x;
+            if face_up {
+                let kind = Some(CardKind::of_side_card(card));
+                self.draw_card(x, area.y, card.label(), kind, frame);
+            } else {
+                self.draw_card(x, area.y, "?".to_string(), None, frame);
+            }
+        }
+    }
+}
+
+// Point `progress` of the way from `from` to `to`, easing out as it arrives
+//
+fn lerp(from: (usize, usize), to: (usize, usize), progress: f32) -> (usize, usize) {
+    let eased = 1.0 - (1.0 - progress).powi(2);
+    let step = |a: usize, b: usize| (a as f32 + (b as f32 - a as f32) * eased).round() as usize;
+
+    (step(from.0, to.0), step(from.1, to.1))
 }
@@ -0,0 +1,292 @@
+// Bots written in any language, run as a separate program.
+//
+// On each of its turns the bot is sent the table as one line of JSON on stdin, from its seat's
+// point of view, and answers with one line on stdout: the turn number from the table, then
+// "hit", "stand" or "play N" for the Nth side card slot, e.g. "7 hit". A bot that takes longer
+// than its timeout, or answers with anything it isn't allowed to do, stands for that turn
+// instead. The number ties an answer to its turn, so one that comes too late is ignored
+// rather than taken for the next turn's.
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+    process::{Child, Command, Stdio},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Receiver, SyncSender},
+    },
+    thread,
+    time::Duration,
+};
+
+use serde::Serialize;
+
+use crate::{
+    BOT_TIMEOUT_MS, ROUNDS_TO_WIN, TARGET_SCORE,
+    card::LogicCard,
+    game::{GameAction, GameState},
+    player::{Player, PlayerState},
+};
+
+/// An external program making a seat's moves. Clones share the one running program
+///
+#[derive(Debug, Clone)]
+pub struct Bot {
+    process: Arc<Mutex<BotProcess>>,
+    name: String,
+    timeout: Duration,
+}
+
+#[derive(Debug)]
+struct BotProcess {
+    child: Child,
+    requests: SyncSender<String>, // lines for stdin, written on their own thread
+    replies: Receiver<String>,    // lines from stdout, disconnected once the bot exits
+    reply: Option<String>,        // an answer to this turn that's not been played yet
+    turn: u64,                    // how many times the bot has been asked for a move
+}
+
+impl Bot {
+    /// Start `command`, a program followed by any arguments separated by spaces
+    ///
+    pub fn spawn(command: &str) -> io::Result<Self> {
+        let mut words = command.split_whitespace();
+        let program = words
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no bot program given"))?;
+
+        // Anything the bot prints to stderr would land on top of the board
+        let mut child = Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(io::Error::other("bot's stdin or stdout went missing"));
+        };
+        // A bot that doesn't read its stdin fills the pipe, which blocks whoever writes to
+        // it, so that's never the game. Only one line waits at a time, more are dropped
+        let (requests, to_write) = mpsc::sync_channel::<String>(1);
+        thread::spawn(move || {
+            let mut stdin = stdin;
+            for line in to_write {
+                if stdin.write_all(line.as_bytes()).is_err() || stdin.flush().is_err() {
+                    break;
+                }
+            }
+        });
+
+        let (tx, replies) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            process: Arc::new(Mutex::new(BotProcess {
+                child,
+                requests,
+                replies,
+                reply: None,
+                turn: 0,
+            })),
+            name: name_of(command),
+            timeout: Duration::from_millis(BOT_TIMEOUT_MS),
+        })
+    }
+
+    /// Give up on each answer after `timeout` rather than the default
+    ///
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Name for the bot's seat, taken from its command
+    ///
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// How long the bot gets to answer
+    ///
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Send the table to the bot, asking for `seat`'s move. Never blocks: a bot that's
+    /// stopped listening, or is still to read what it was last sent, just never answers
+    ///
+    pub fn ask(&self, state: &GameState, seat: Player) {
+        let Ok(mut process) = self.process.lock() else {
+            return;
+        };
+
+        process.reply = None;
+        process.turn += 1;
+
+        let line = format!("{}\n", state_line(state, seat, process.turn));
+        let _ = process.requests.try_send(line);
+    }
+
+    /// Has the bot answered since it was last asked?
+    ///
+    pub fn has_answered(&self) -> bool {
+        let Ok(mut process) = self.process.lock() else {
+            return false;
+        };
+        if process.reply.is_none() {
+            // Answers to earlier turns, which missed their timeout, are dropped
+            let turn = process.turn;
+            process.reply = process
+                .replies
+                .try_iter()
+                .find_map(|line| answer_to(&line, turn));
+        }
+        process.reply.is_some()
+    }
+
+    /// The bot's answer, if it's given one that reads as a move
+    ///
+    pub fn take_answer(&self) -> Option<GameAction> {
+        self.has_answered();
+        let reply = self.process.lock().ok()?.reply.take()?;
+        parse_action(&reply)
+    }
+}
+
+// Clones share a process, so they're the same bot
+impl PartialEq for Bot {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.process, &other.process)
+    }
+}
+
+impl Eq for Bot {}
+
+impl Drop for BotProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// Interpreters a bot's script is run with, so the bot is named after the script instead
+const INTERPRETERS: [&str; 12] = [
+    "env", "sh", "bash", "python", "python2", "python3", "node", "deno", "ruby", "perl", "php",
+    "java",
+];
+
+/// Name for a bot run with `command`: the program's file name without its extension, or the
+/// script's if the program is an interpreter, e.g. "mybot" for "python3 mybot.py --level 3"
+///
+pub fn name_of(command: &str) -> String {
+    let mut words = command.split_whitespace();
+    let stem =
+        |word: Option<&str>| Some(Path::new(word?).file_stem()?.to_string_lossy().into_owned());
+
+    let mut name = stem(words.next());
+    while name
+        .as_deref()
+        .is_some_and(|name| INTERPRETERS.contains(&name))
+    {
+        // Flags to the interpreter come before the script
+        name = stem(words.find(|word| !word.starts_with('-')));
+    }
+    name.unwrap_or_else(|| "Bot".to_string())
+}
+
+// The move in `line` if it answers `turn`, e.g. "hit" from "7 hit" on turn 7
+fn answer_to(line: &str, turn: u64) -> Option<String> {
+    let (number, answer) = line.trim().split_once(' ')?;
+    (number.parse::<u64>().ok()? == turn).then(|| answer.to_string())
+}
+
+/// Read a move: "hit", "stand" or "play N", where N counts side card slots from 1
+///
+pub fn parse_action(line: &str) -> Option<GameAction> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+
+    match words.as_slice() {
+        ["hit"] => Some(GameAction::Hit),
+        ["stand"] => Some(GameAction::Stand),
+        ["play", slot] => {
+            let slot = slot.parse::<usize>().ok()?;
+            Some(GameAction::PlayHand {
+                index: slot.checked_sub(1)?,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The table as `seat` sees it on the bot's `turn`th move, as a line of JSON:
+/// their own side cards but only how many the other seat is holding
+///
+pub fn state_line(state: &GameState, seat: Player, turn: u64) -> String {
+    let view = BotView {
+        turn,
+        target_score: TARGET_SCORE,
+        rounds_to_win: ROUNDS_TO_WIN,
+        you: SideView::of(&state.seats[seat], true),
+        opponent: SideView::of(&state.seats[seat.other()], false),
+    };
+
+    // Every field serializes to plain JSON, so this can't fail
+    serde_json::to_string(&view).unwrap_or_default()
+}
+
+#[derive(Serialize)]
+struct BotView<'a> {
+    turn: u64, // echoed at the start of the answer
+    target_score: i32,
+    rounds_to_win: usize,
+    you: SideView<'a>,
+    opponent: SideView<'a>,
+}
+
+#[derive(Serialize)]
+struct SideView<'a> {
+    name: &'a str,
+    score: i32,
+    dealer_row: Vec<i32>,
+    played_row: Vec<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hand: Option<Vec<Option<i32>>>, // slot by slot, empty once played; only for the bot's seat
+    cards_in_hand: usize,
+    stood: bool,
+    bust: bool,
+    rounds_won: usize,
+}
+
+impl<'a> SideView<'a> {
+    fn of(side: &'a PlayerState, show_hand: bool) -> Self {
+        Self {
+            name: &side.name,
+            score: side.score(),
+            dealer_row: values(&side.dealer_row),
+            played_row: values(&side.played_row),
+            hand: show_hand.then(|| {
+                side.hand
+                    .iter()
+                    .map(|slot| slot.map(|card| card.value))
+                    .collect()
+            }),
+            cards_in_hand: side.hand.iter().flatten().count(),
+            stood: side.stood,
+            bust: side.bust,
+            rounds_won: side.rounds_won,
+        }
+    }
+}
+
+fn values(cards: &[LogicCard]) -> Vec<i32> {
+    cards.iter().map(|card| card.value).collect()
+}
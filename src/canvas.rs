@@ -0,0 +1,190 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    frame::{Cell, Frame, Style},
+    layout::{Align, Rect},
+};
+
+/// Characters used to outline a box
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BoxGlyphs {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+impl BoxGlyphs {
+    pub const ASCII: BoxGlyphs = BoxGlyphs {
+        top_left: '+',
+        top_right: '+',
+        bottom_left: '+',
+        bottom_right: '+',
+        horizontal: '-',
+        vertical: '|',
+    };
+
+    pub const ROUNDED: BoxGlyphs = BoxGlyphs {
+        top_left: '╭',
+        top_right: '╮',
+        bottom_left: '╰',
+        bottom_right: '╯',
+        horizontal: '─',
+        vertical: '│',
+    };
+}
+
+/// Number of terminal columns `text` takes up.
+/// Wide characters (e.g. CJK, most emoji) take two, combining marks none.
+///
+pub fn text_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Drawing primitives over a Frame.
+/// Anything that would land outside the frame is dropped, so callers never need bounds checks.
+///
+pub struct Canvas<'a> {
+    frame: &'a mut Frame,
+}
+
+impl<'a> Canvas<'a> {
+    pub fn new(frame: &'a mut Frame) -> Self {
+        Self { frame }
+    }
+
+    /// The whole frame
+    ///
+    pub fn area(&self) -> Rect {
+        let height = self.frame.first().map_or(0, |col| col.len());
+        Rect::new(0, 0, self.frame.len(), height)
+    }
+
+    /// Cell at (x, y), if it's on the frame
+    ///
+    pub fn get(&self, x: usize, y: usize) -> Option<&Cell> {
+        self.frame.get(x).and_then(|col| col.get(y))
+    }
+
+    /// Set a single cell, ignored if it's off the frame.
+    /// Overwriting either half of a wide character blanks the other half.
+    ///
+    pub fn set(&mut self, x: usize, y: usize, cell: Cell) {
+        let Some(old) = self.get(x, y).copied() else {
+            return;
+        };
+
+        if old.is_continuation() && !cell.is_continuation() && x > 0 {
+            self.blank(x - 1, y);
+        }
+        if old.ch.width() == Some(2) && self.get(x + 1, y).is_some_and(Cell::is_continuation) {
+            self.blank(x + 1, y);
+        }
+
+        self.frame[x][y] = cell;
+    }
+
+    // Replace a cell with a space in the same style, without fixing up wide characters
+    fn blank(&mut self, x: usize, y: usize) {
+        let cell = &mut self.frame[x][y];
+        *cell = Cell::new(' ', cell.style);
+    }
+
+    /// Draw text starting at (x, y), clipped to the frame.
+    /// Returns the number of columns the text would take up.
+    ///
+    pub fn text(&mut self, text: &str, x: usize, y: usize, style: Style) -> usize {
+        let max_width = self.frame.len().saturating_sub(x);
+        self.text_clipped(text, x, y, max_width, style);
+
+        text_width(text)
+    }
+
+    /// Draw text on the first row of `area`, aligned within it and clipped to its width
+    ///
+    pub fn text_in(&mut self, text: &str, area: Rect, align: Align, style: Style) {
+        if area.height == 0 {
+            return;
+        }
+
+        let width = text_width(text).min(area.width);
+        let x = area.align_x(width, align);
+        self.text_clipped(text, x, area.y, width, style);
+    }
+
+    /// Draw text centered on column `x`
+    ///
+    pub fn text_centered(&mut self, text: &str, x: usize, y: usize, style: Style) {
+        let left = x.saturating_sub(text_width(text) / 2);
+        self.text(text, left, y, style);
+    }
+
+    /// Paint every cell of `area` with `cell`
+    ///
+    pub fn fill(&mut self, area: Rect, cell: Cell) {
+        for x in area.x..area.right() {
+            for y in area.y..area.bottom() {
+                self.set(x, y, cell);
+            }
+        }
+    }
+
+    /// Outline `area` with a box, leaving its inside untouched
+    ///
+    pub fn border(&mut self, area: Rect, glyphs: BoxGlyphs, style: Style) {
+        if area.width < 2 || area.height < 2 {
+            return;
+        }
+
+        let (x0, x1) = (area.x, area.right() - 1);
+        let (y0, y1) = (area.y, area.bottom() - 1);
+
+        for x in x0..=x1 {
+            self.set(x, y0, Cell::new(glyphs.horizontal, style));
+            self.set(x, y1, Cell::new(glyphs.horizontal, style));
+        }
+        for y in y0..=y1 {
+            self.set(x0, y, Cell::new(glyphs.vertical, style));
+            self.set(x1, y, Cell::new(glyphs.vertical, style));
+        }
+
+        self.set(x0, y0, Cell::new(glyphs.top_left, style));
+        self.set(x1, y0, Cell::new(glyphs.top_right, style));
+        self.set(x0, y1, Cell::new(glyphs.bottom_left, style));
+        self.set(x1, y1, Cell::new(glyphs.bottom_right, style));
+    }
+
+    /// Blank `area` and outline it with a box
+    ///
+    pub fn panel(&mut self, area: Rect, glyphs: BoxGlyphs, style: Style) {
+        self.fill(area, Cell::new(' ', style));
+        self.border(area, glyphs, style);
+    }
+
+    // Draw at most `max_width` columns of text.
+    // A wide character that would be cut in half is left out rather than split.
+    fn text_clipped(&mut self, text: &str, x: usize, y: usize, max_width: usize, style: Style) {
+        let mut col = 0;
+
+        for ch in text.chars() {
+            let width = match ch.width() {
+                Some(width) if width > 0 => width,
+                // combining marks and control characters have no column of their own
+                _ => continue,
+            };
+
+            if col + width > max_width {
+                break;
+            }
+
+            self.set(x + col, y, Cell::new(ch, style));
+            for extra in 1..width {
+                self.set(x + col + extra, y, Cell::continuation(style));
+            }
+            col += width;
+        }
+    }
+}
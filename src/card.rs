@@ -1,11 +1,145 @@
-use crate::{CARD_HEIGHT, CARD_WIDTH, frame::{Drawable, Frame}};
+use std::{env, fmt};
+
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::{
+    CARD_HEIGHT, CARD_WIDTH, SMALL_CARD_HEIGHT, SMALL_CARD_WIDTH, TOKEN_WIDTH,
+    canvas::{BoxGlyphs, Canvas},
+    frame::{Cell, Drawable, Frame},
+    layout::{Align, Rect},
+    theme::{Role, Theme},
+};
 
 #[derive(Debug, Copy, Clone)]
 pub enum CardKind {
-    Dealer,           // only +1..+10
-    PlayerPlus,       // Player +1..+6
-    PlayerMinus,      // Player -1..-6
-    //PlayerPlusMinus,  // Player +-1..+-6 so we can 'flip' its value
+    Dealer,          // only +1..+10
+    PlayerPlus,      // Player +1..+6
+    PlayerMinus,     // Player -1..-6
+    PlayerPlusMinus, // Player +-1..+-6 so we can 'flip' its value
+}
+
+impl CardKind {
+    /// Side cards are plus or minus depending on the sign of their value,
+    /// unless they can be flipped to count either way
+    ///
+    pub fn of_side_card(card: &LogicCard) -> Self {
+        if card.flippable {
+            CardKind::PlayerPlusMinus
+        } else if card.value < 0 {
+            CardKind::PlayerMinus
+        } else {
+            CardKind::PlayerPlus
+        }
+    }
+
+    /// Theme role used to draw cards of this kind
+    ///
+    pub fn role(&self) -> Role {
+        match self {
+            CardKind::Dealer => Role::DealerCard,
+            CardKind::PlayerPlus => Role::PlusCard,
+            CardKind::PlayerMinus => Role::MinusCard,
+            CardKind::PlayerPlusMinus => Role::PlusMinusCard,
+        }
+    }
+
+    /// Suit-like glyph marking the kind in the corners of Unicode cards
+    ///
+    pub fn suit(&self) -> char {
+        match self {
+            CardKind::Dealer => '♦',
+            CardKind::PlayerPlus => '♠',
+            CardKind::PlayerMinus => '♥',
+            CardKind::PlayerPlusMinus => '♣',
+        }
+    }
+}
+
+/// How card outlines are drawn
+///
+#[derive(EnumIter, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CardStyle {
+    Ascii,   // '+', '-' and '|', works everywhere
+    Unicode, // rounded box-drawing outline with suit glyphs
+}
+
+impl CardStyle {
+    /// Use Unicode cards only when the terminal locale is UTF-8
+    ///
+    pub fn from_env() -> Self {
+        // The first locale variable that is set wins, as in setlocale(3)
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        if locale.contains("utf-8") || locale.contains("utf8") {
+            CardStyle::Unicode
+        } else {
+            CardStyle::Ascii
+        }
+    }
+
+    /// Outline used for cards drawn in this style
+    ///
+    pub fn glyphs(&self) -> BoxGlyphs {
+        match self {
+            CardStyle::Ascii => BoxGlyphs::ASCII,
+            CardStyle::Unicode => BoxGlyphs::ROUNDED,
+        }
+    }
+
+    // Only Unicode cards have suit glyphs in their corners
+    fn suits(&self) -> bool {
+        matches!(self, CardStyle::Unicode)
+    }
+}
+
+impl Default for CardStyle {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+/// Implement display for CardStyle enum to show names in settings
+///
+impl fmt::Display for CardStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardStyle::Ascii => write!(f, "ASCII"),
+            CardStyle::Unicode => write!(f, "Unicode"),
+        }
+    }
+}
+
+/// How big cards are drawn, from full cards down to one-line tokens
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CardSize {
+    Full,  // CARD_WIDTH x CARD_HEIGHT
+    Small, // SMALL_CARD_WIDTH x SMALL_CARD_HEIGHT
+    Token, // single line, e.g. "[+3]"
+}
+
+impl CardSize {
+    pub fn width(&self) -> usize {
+        match self {
+            CardSize::Full => CARD_WIDTH,
+            CardSize::Small => SMALL_CARD_WIDTH,
+            CardSize::Token => TOKEN_WIDTH,
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        match self {
+            CardSize::Full => CARD_HEIGHT,
+            CardSize::Small => SMALL_CARD_HEIGHT,
+            CardSize::Token => 1,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -15,10 +149,39 @@ pub enum Owner {
     Dealer,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LogicCard {
     pub value: i32,
-    // pub kind: CardKind,
+    #[serde(default)]
+    pub flippable: bool, // a ± side card, which can be played as +value or -value
+}
+
+impl LogicCard {
+    pub fn new(value: i32) -> Self {
+        Self {
+            value,
+            flippable: false,
+        }
+    }
+
+    /// A ± side card, counting as `value` unless it's flipped when played
+    ///
+    pub fn plus_minus(value: i32) -> Self {
+        Self {
+            value,
+            flippable: true,
+        }
+    }
+
+    /// The text on the card: its value with a sign, or both signs for a ± card
+    ///
+    pub fn label(&self) -> String {
+        if self.flippable {
+            format!("±{}", self.value.abs())
+        } else {
+            format!("{:+}", self.value)
+        }
+    }
 }
 
 pub struct CardView {
@@ -27,6 +190,9 @@ pub struct CardView {
     // pub width: usize,
     // pub height: usize,
     pub text: String,
+    pub kind: Option<CardKind>, // None for cards that don't show their kind, e.g. hidden cards
+    pub style: CardStyle,
+    pub size: CardSize,
 }
 
 impl CardView {
@@ -36,64 +202,50 @@ impl CardView {
     //     }
     //
     //     self.value.to_string()
-    // } 
+    // }
 }
 
 impl Drawable for CardView {
-    fn draw(&self, frame: &mut Frame) {
-        let x0 = self.x;
-        let y0 = self.y;
+    fn draw(&self, frame: &mut Frame, theme: Theme) {
+        let width = self.size.width();
+        let height = self.size.height();
+        let area = Rect::new(self.x, self.y, width, height);
 
-        if x0 >= frame.len() || y0 >= frame[0].len() {
-            return;
-        }
+        let mut canvas = Canvas::new(frame);
 
-        if x0 + CARD_WIDTH > frame.len() || y0 + CARD_HEIGHT > frame[0].len() {
+        // cards that don't fit are left out rather than drawn cut off
+        let frame_area = canvas.area();
+        if area.right() > frame_area.right() || area.bottom() > frame_area.bottom() {
             return;
         }
 
-        let x1 = x0 + CARD_WIDTH - 1;
-        let y1 = y0 + CARD_HEIGHT - 1;
-
-        // borders
-        for x in x0..=x1 {
-            frame[x][y0] = '-';
-            frame[x][y1] = '-';
-        }
-
-        for y in y0..=y1 {
-            frame[x0][y] = '|';
-            frame[x1][y] = '|';
-        }
+        let role = self.kind.map_or(Role::CardBorder, |kind| kind.role());
+        let style = theme.style(role);
 
-        // corners
-        frame[x0][y0] = '+';
-        frame[x1][y0] = '+';
-        frame[x0][y1] = '+';
-        frame[x1][y1] = '+';
-
-        // interior
-        for x in (x0 + 1)..x1 {
-            for y in (y0 + 1)..y1 {
-                frame[x][y] = ' ';
-            }
+        // tokens are just the text in brackets, e.g. "[+3]"
+        if let CardSize::Token = self.size {
+            canvas.set(area.x, area.y, Cell::new('[', style.bold()));
+            canvas.text_in(&self.text, area.pad(1, 0), Align::End, style.bold());
+            canvas.set(area.right() - 1, area.y, Cell::new(']', style.bold()));
+            return;
         }
 
-        // centered text
-        let inner_width = CARD_WIDTH - 2;
-        let text_y = y0 + CARD_HEIGHT / 2;
-
-        // clamp to available space
-        let text = if self.text.len() > inner_width {
-            self.text[..inner_width].to_string()
-        } else {
-            self.text.clone()
-        };
-
-        let start_x = x0 + 1 + (inner_width - text.len()) / 2;
-
-        for (i, ch) in text.chars().enumerate() {
-            frame[start_x + i][text_y] = ch;
+        // outline with a blank interior
+        canvas.panel(area, self.style.glyphs(), style);
+
+        // centered text, clamped to the space inside the border
+        let text_row = area.pad(1, 0).row(height / 2);
+        canvas.text_in(&self.text, text_row, Align::Center, style.bold());
+
+        // suits in opposite corners, like a real card, if there's room around the text
+        if self.style.suits()
+            && height >= 5
+            && let Some(kind) = self.kind
+        {
+            let inner = area.pad(1, 1);
+            let suit = Cell::new(kind.suit(), style);
+            canvas.set(inner.x, inner.y, suit);
+            canvas.set(inner.right() - 1, inner.bottom() - 1, suit);
         }
     }
 }
@@ -0,0 +1,116 @@
+// Chat typed during networked and hot-seat games, with numbered quick emotes
+
+use crossterm::event::KeyCode;
+
+use crate::{CHAT_MAX_LEN, EMOTES};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatAction {
+    Open,
+    Type(char),
+    Erase,
+    Send,
+    Cancel,
+}
+
+/// The chat line at this end of the table. While it's open every key is typed into it,
+/// so none of the game's single key bindings fire
+///
+#[derive(Debug, Default)]
+pub struct Chat {
+    input: Option<String>, // the line being typed, while open
+    pub log: Vec<String>,  // hot-seat: everything said, oldest first
+}
+
+impl Chat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.input.is_some()
+    }
+
+    /// What's been typed so far, while the chat line is open
+    ///
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_deref()
+    }
+
+    /// 't' opens the chat line. Once open, Enter sends it and Esc closes it
+    ///
+    pub fn handle_input(&self, key: KeyCode) -> Option<ChatAction> {
+        if !self.is_open() {
+            return (key == KeyCode::Char('t')).then_some(ChatAction::Open);
+        }
+
+        match key {
+            KeyCode::Enter => Some(ChatAction::Send),
+            KeyCode::Esc => Some(ChatAction::Cancel),
+            KeyCode::Backspace => Some(ChatAction::Erase),
+            KeyCode::Char(c) => Some(ChatAction::Type(c)),
+            _ => None,
+        }
+    }
+
+    /// Returns the message to say once the line is sent, if there's anything to say
+    ///
+    pub fn apply_action(&mut self, action: ChatAction) -> Option<String> {
+        match action {
+            ChatAction::Open => self.input = Some(String::new()),
+            ChatAction::Type(c) => {
+                if let Some(input) = &mut self.input
+                    && input.chars().count() < CHAT_MAX_LEN
+                {
+                    input.push(c);
+                }
+            }
+            ChatAction::Erase => {
+                if let Some(input) = &mut self.input {
+                    input.pop();
+                }
+            }
+            ChatAction::Send => return message(self.input.take()?.trim()),
+            ChatAction::Cancel => self.input = None,
+        }
+
+        None
+    }
+}
+
+/// What a finished chat line says: a number on its own picks that quick emote,
+/// and a blank line says nothing
+///
+pub fn message(line: &str) -> Option<String> {
+    if line.is_empty() {
+        return None;
+    }
+
+    let emote = line
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| EMOTES.get(number.checked_sub(1)?));
+
+    Some(emote.copied().unwrap_or(line).to_string())
+}
+
+/// Text from the other end made safe to show: control characters, which could
+/// drive the terminal, are dropped and it's cut to CHAT_MAX_LEN characters
+///
+pub fn clean(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control())
+        .take(CHAT_MAX_LEN)
+        .collect()
+}
+
+/// The quick emotes with their numbers, e.g. "1 Nice hand  2 Ouch"
+///
+pub fn emote_hint() -> String {
+    EMOTES
+        .iter()
+        .enumerate()
+        .map(|(i, emote)| format!("{} {emote}", i + 1))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
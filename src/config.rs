@@ -1,6 +1,6 @@
 pub(crate) use crossterm::terminal;
 
-use crate::{CARD_HEIGHT, CARD_WIDTH, H_PAD, MIN_CARD_SIZE_HEIGHT, MIN_CARD_SIZE_WIDTH, V_PAD};
+use crate::board::BoardLayout;
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -12,28 +12,41 @@ impl Config {
     // Return error so that program exits if terminal size is too small
     pub fn from_terminal() -> anyhow::Result<Self> {
         let (cols, rows) = terminal::size()?;
+        let config = Self::from_size(cols, rows);
+        let (min_cols, min_rows) = Self::min_size();
 
-        let cols = cols as usize;
-        let rows = rows as usize;
-
-        let min_cols = CARD_WIDTH * MIN_CARD_SIZE_WIDTH + H_PAD;
-        let min_rows = CARD_HEIGHT * MIN_CARD_SIZE_HEIGHT + V_PAD;
-
-        if cols < min_cols || rows < min_rows {
+        if config.is_too_small() {
             anyhow::bail!(
                 "Your terminal is too small!\n\
                 Minimum height required: {}x{}\n\
                 Current size: {}x{}\n",
                 min_cols,
                 min_rows,
-                cols,
-                rows
+                config.num_cols,
+                config.num_rows
             );
         }
 
-        Ok(Self {
-            num_cols: cols,
-            num_rows: rows,
-        })
+        Ok(config)
+    }
+
+    // Config for a given terminal size, e.g. after a resize event.
+    // May be too small to play in, see is_too_small()
+    pub fn from_size(cols: u16, rows: u16) -> Self {
+        Self {
+            num_cols: cols as usize,
+            num_rows: rows as usize,
+        }
+    }
+
+    // Smallest (cols, rows) the game can be drawn in, using the most compact board layout
+    pub fn min_size() -> (usize, usize) {
+        BoardLayout::Tokens.min_size()
+    }
+
+    pub fn is_too_small(&self) -> bool {
+        let (min_cols, min_rows) = Self::min_size();
+
+        self.num_cols < min_cols || self.num_rows < min_rows
     }
 }
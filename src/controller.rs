@@ -0,0 +1,96 @@
+// Who makes the moves for a seat, and the computer's strategy
+
+use std::{collections::VecDeque, fmt};
+
+use crate::{
+    STAND_THRESHOLD, TARGET_SCORE,
+    bot::Bot,
+    card::LogicCard,
+    game::{GameAction, GameState},
+    player::Player,
+};
+
+/// Where a seat's moves come from
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Controller {
+    Human,                        // keys pressed at this terminal
+    Computer,                     // the built in strategy, after a moment's thought
+    Network,                      // a peer over the network, applied as their moves arrive
+    Script(VecDeque<GameAction>), // these moves in order, then the computer's strategy
+    Bot(Bot),                     // an external program, standing if it's slow or wrong
+}
+
+impl Controller {
+    pub fn is_human(&self) -> bool {
+        matches!(self, Controller::Human)
+    }
+
+    /// Does the game make this seat's moves itself, without waiting on anyone?
+    ///
+    pub fn is_automatic(&self) -> bool {
+        matches!(
+            self,
+            Controller::Computer | Controller::Script(_) | Controller::Bot(_)
+        )
+    }
+
+    /// Has a bot sent its move, so there's no need to wait out its time any longer?
+    ///
+    pub fn has_answered(&self) -> bool {
+        matches!(self, Controller::Bot(bot) if bot.has_answered())
+    }
+}
+
+impl fmt::Display for Controller {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Controller::Human => write!(f, "human"),
+            Controller::Computer => write!(f, "computer"),
+            Controller::Network => write!(f, "network"),
+            Controller::Script(moves) => write!(f, "script ({} left)", moves.len()),
+            Controller::Bot(bot) => write!(f, "bot ({})", bot.name()),
+        }
+    }
+}
+
+/// The computer's move for `seat`:
+/// play a side card that makes exactly 20, either way up if it's a ± card,
+/// stand on 17 or more, otherwise hit
+///
+pub fn computer_move(state: &GameState, seat: Player) -> GameAction {
+    let side = &state.seats[seat];
+    let score = side.score();
+    let target = TARGET_SCORE - score;
+
+    let card_hits_twenty = |card: &LogicCard| -> bool { card.value == target };
+
+    if let Some(index) = first_hand_index(&side.hand, card_hits_twenty) {
+        return GameAction::PlayHand { index };
+    }
+
+    let flipped_hits_twenty =
+        |card: &LogicCard| -> bool { card.flippable && -card.value == target };
+
+    if let Some(index) = first_hand_index(&side.hand, flipped_hits_twenty) {
+        return GameAction::PlayFlipped { index };
+    }
+
+    // if score is >= 17, stand
+    if score >= STAND_THRESHOLD as i32 {
+        return GameAction::Stand;
+    }
+
+    GameAction::Hit
+}
+
+/// Helper to finds first occurrence of card in hand that matches predicate
+///
+fn first_hand_index<P>(hand: &[Option<LogicCard>], mut pred: P) -> Option<usize>
+where
+    P: FnMut(&LogicCard) -> bool,
+{
+    hand.iter()
+        .enumerate()
+        .find_map(|(i, slot)| slot.as_ref().filter(|card| pred(card)).map(|_| i))
+}
@@ -0,0 +1,151 @@
+// Putting the terminal back when the game exits or crashes, and writing crash reports
+
+use std::{
+    backtrace::Backtrace,
+    env, fs, io, panic,
+    path::PathBuf,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::{
+    ExecutableCommand,
+    cursor::{Hide, Show},
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::shuffle;
+
+// Whether the terminal is currently in raw mode on the alternate screen
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+// What the game was last doing, for the crash report
+static CONTEXT: Mutex<Option<CrashContext>> = Mutex::new(None);
+
+/// Puts the terminal into raw mode on the alternate screen, and back again when dropped.
+/// Dropping happens on error returns and while unwinding from a panic, so the terminal
+/// is never left unusable
+///
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn enter() -> io::Result<Self> {
+        TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        // Built first so a failure part way through still undoes whatever was done
+        let guard = Self;
+
+        terminal::enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        stdout.execute(EnterAlternateScreen)?;
+        stdout.execute(Hide)?; // Hide cursor
+
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Show the cursor, leave the alternate screen and disable raw mode.
+/// Safe to call more than once, only the first call after entering does anything
+///
+pub fn restore_terminal() {
+    if !TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    // Nothing more can be done if these fail, so carry on with the rest regardless
+    let mut stdout = io::stdout();
+    let _ = stdout.execute(Show);
+    let _ = stdout.execute(LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+}
+
+/// What the game was doing, kept up to date by the game loop
+///
+#[derive(Debug, Clone, Default)]
+pub struct CrashContext {
+    pub screen: &'static str,
+    pub seed: Option<[u8; 32]>, // seed of the deck being dealt from
+    pub events: Vec<String>,    // recent game events, oldest first
+}
+
+/// Remember what the game is doing in case it crashes
+///
+pub fn set_context(context: CrashContext) {
+    if let Ok(mut current) = CONTEXT.lock() {
+        *current = Some(context);
+    }
+}
+
+/// On a panic in any thread: restore the terminal, write a crash report
+/// and print where it went
+///
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        restore_terminal();
+
+        let backtrace = Backtrace::force_capture();
+        // try_lock, as the panic may have happened while the context was being set
+        let context = match CONTEXT.try_lock() {
+            Ok(context) => context.clone().unwrap_or_default(),
+            Err(_) => CrashContext::default(),
+        };
+        let report = crash_report(&info.to_string(), &backtrace.to_string(), &context);
+
+        eprintln!("kaazap crashed: {info}");
+        match write_report(&report) {
+            Ok(path) => eprintln!("Crash report written to {}", path.display()),
+            Err(err) => eprintln!("Couldn't write a crash report ({err}):\n\n{report}"),
+        }
+    }));
+}
+
+/// Text of a crash report
+///
+pub fn crash_report(message: &str, backtrace: &str, context: &CrashContext) -> String {
+    let seed = match context.seed {
+        Some(seed) => shuffle::to_hex(&seed),
+        None => "none".to_string(),
+    };
+    let screen = match context.screen {
+        "" => "unknown",
+        screen => screen,
+    };
+
+    let mut report = format!(
+        "kaazap {} crash report\n\n\
+         Panic:\n{message}\n\n\
+         Screen: {screen}\n\
+         Seed: {seed}\n\n\
+         Recent events:\n",
+        env!("CARGO_PKG_VERSION"),
+    );
+
+    if context.events.is_empty() {
+        report.push_str("  none\n");
+    }
+    for event in &context.events {
+        report.push_str(&format!("  {event}\n"));
+    }
+
+    report.push_str(&format!("\nBacktrace:\n{backtrace}\n"));
+    report
+}
+
+// Write the report to a new file in the temp directory, returning its path
+fn write_report(report: &str) -> io::Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    let path = env::temp_dir().join(format!("kaazap-crash-{timestamp}.txt"));
+
+    fs::write(&path, report)?;
+    Ok(path)
+}
@@ -0,0 +1,149 @@
+// Debug overlay toggled with F12, drawn on top of whatever screen is showing
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    canvas::{BoxGlyphs, Canvas},
+    controller::Controller,
+    frame::Frame,
+    game::GameState,
+    layout::Align,
+    player::{Player, PlayerState},
+    render::RenderStats,
+    theme::{Role, Theme},
+};
+
+// How far back frames are counted towards the frame rate
+const FPS_WINDOW: Duration = Duration::from_secs(1);
+
+// Number of game events listed in the overlay
+const EVENTS_SHOWN: usize = 5;
+
+const OVERLAY_WIDTH: usize = 40;
+
+/// Frame timing and render stats, shown with the game state when visible
+///
+#[derive(Debug, Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+    frame_times: VecDeque<Instant>, // frames drawn within the last FPS_WINDOW
+    last_render: RenderStats,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Note that a frame was drawn at `now`
+    ///
+    pub fn record_frame(&mut self, now: Instant) {
+        while let Some(&oldest) = self.frame_times.front()
+            && now.duration_since(oldest) > FPS_WINDOW
+        {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(now);
+    }
+
+    /// Keep what the renderer reported for the latest frame
+    ///
+    pub fn record_render(&mut self, stats: RenderStats) {
+        self.last_render = stats;
+    }
+
+    /// Frames drawn over the last second.
+    /// Frames are only drawn when something changes, so this sits near zero when idle
+    ///
+    pub fn fps(&self) -> usize {
+        self.frame_times.len()
+    }
+
+    /// Lines of text for the overlay, with game details if a game is running
+    ///
+    pub fn lines(&self, screen: &str, game_state: Option<&GameState>) -> Vec<String> {
+        let mut lines = vec![
+            format!("FPS: {}", self.fps()),
+            format!(
+                "Cells: {}  Bytes: {}",
+                self.last_render.cells_changed, self.last_render.bytes_written
+            ),
+            format!("Screen: {screen}"),
+        ];
+
+        let Some(state) = game_state else {
+            return lines;
+        };
+
+        lines.push(format!("Phase: {}", state.game_phase));
+        for (seat, side) in state.seats.iter() {
+            lines.push(side_flags(seat, &state.controllers[seat], side));
+        }
+        lines.push(match state.deck_remaining() {
+            Some(left) => format!("Deck: {left} left"),
+            None => "Deck: random".to_string(),
+        });
+
+        lines.push("Events:".to_string());
+        let events = state.recent_events().collect::<Vec<_>>();
+        let skip = events.len().saturating_sub(EVENTS_SHOWN);
+        lines.extend(events[skip..].iter().map(|event| format!(" {event}")));
+
+        lines
+    }
+
+    /// Draw the overlay in the top right corner, if it's visible
+    ///
+    pub fn draw(
+        &self,
+        screen: &str,
+        game_state: Option<&GameState>,
+        frame: &mut Frame,
+        theme: Theme,
+    ) {
+        if !self.visible {
+            return;
+        }
+
+        let lines = self.lines(screen, game_state);
+        let mut canvas = Canvas::new(frame);
+        let area = canvas
+            .area()
+            .align(OVERLAY_WIDTH, lines.len() + 2, Align::End, Align::Start);
+
+        let style = theme.style(Role::Text);
+        canvas.panel(area, BoxGlyphs::ASCII, style);
+        canvas.text_in(" debug ", area, Align::Center, theme.style(Role::Title));
+
+        let inside = area.pad(1, 1);
+        for (row, line) in lines.iter().enumerate() {
+            canvas.text_in(line, inside.row(row), Align::Start, style);
+        }
+    }
+}
+
+// e.g. "Player (human): stood bust played"
+fn side_flags(seat: Player, controller: &Controller, side: &PlayerState) -> String {
+    let name = format!("{seat:?} ({controller})");
+    let flags = [
+        (side.stood, "stood"),
+        (side.bust, "bust"),
+        (side.played_card, "played"),
+    ]
+    .into_iter()
+    .filter_map(|(set, flag)| set.then_some(flag))
+    .collect::<Vec<_>>();
+
+    if flags.is_empty() {
+        format!("{name}: -")
+    } else {
+        format!("{name}: {}", flags.join(" "))
+    }
+}
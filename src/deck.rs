@@ -0,0 +1,150 @@
+// Sources of dealer cards
+
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::card::LogicCard;
+
+/// Anything the game can draw dealer cards from
+///
+pub trait DeckSource: fmt::Debug + Send {
+    fn deal(&mut self) -> LogicCard;
+
+    /// Cards left to deal, or None for decks that never run out
+    fn remaining(&self) -> Option<usize> {
+        None
+    }
+
+    /// Seed the deck was shuffled from, or None if it isn't random
+    fn seed(&self) -> Option<[u8; 32]> {
+        None
+    }
+}
+
+/// Default deck: every dealer card is a fresh random value.
+/// Cards come from a seeded ChaCha20 generator, whose output is fixed by its spec rather than
+/// by a rand version, so any build deals a game again from its seed
+///
+#[derive(Debug)]
+pub struct RandomDeck {
+    seed: [u8; 32],
+    rng: ChaCha20Rng,
+}
+
+impl RandomDeck {
+    pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: [u8; 32]) -> Self {
+        Self {
+            seed,
+            rng: ChaCha20Rng::from_seed(seed),
+        }
+    }
+}
+
+impl Default for RandomDeck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeckSource for RandomDeck {
+    // 0 to 10 straight from the generator's output, not rand's range sampling, which may
+    // change between versions. Words from the top of the range that would favour the low
+    // values are skipped
+    fn deal(&mut self) -> LogicCard {
+        const VALUES: u32 = 11;
+        let fair = u32::MAX - u32::MAX % VALUES;
+
+        loop {
+            let word = self.rng.next_u32();
+            if word < fair {
+                return LogicCard::new((word % VALUES) as i32);
+            }
+        }
+    }
+
+    fn seed(&self) -> Option<[u8; 32]> {
+        Some(self.seed)
+    }
+}
+
+/// Rigged deck that deals a predetermined sequence of cards,
+/// falling back to random cards once the script runs out
+///
+#[derive(Debug, Default)]
+pub struct ScriptedDeck {
+    cards: VecDeque<LogicCard>,
+    fallback: RandomDeck,
+}
+
+impl ScriptedDeck {
+    pub fn new(values: &[i32]) -> Self {
+        Self {
+            cards: values.iter().map(|&value| LogicCard::new(value)).collect(),
+            fallback: RandomDeck::new(),
+        }
+    }
+}
+
+impl DeckSource for ScriptedDeck {
+    fn deal(&mut self) -> LogicCard {
+        self.cards
+            .pop_front()
+            .unwrap_or_else(|| self.fallback.deal())
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        Some(self.cards.len())
+    }
+
+    // Only matters once the script has run out
+    fn seed(&self) -> Option<[u8; 32]> {
+        self.fallback.seed()
+    }
+}
+
+/// Deals the cards it's handed, in order: a networked client deals whatever the host dealt.
+/// Clones share the same cards, so keep one to hand cards to after giving the other to the game
+///
+#[derive(Debug, Default, Clone)]
+pub struct RelayedDeck {
+    cards: Arc<Mutex<VecDeque<LogicCard>>>,
+}
+
+impl RelayedDeck {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue `card` to be the next one dealt
+    ///
+    pub fn push(&self, card: LogicCard) {
+        if let Ok(mut cards) = self.cards.lock() {
+            cards.push_back(card);
+        }
+    }
+}
+
+impl DeckSource for RelayedDeck {
+    // Only ever dealt from after a card has been pushed, a blank is a safe fallback regardless
+    fn deal(&mut self) -> LogicCard {
+        self.cards
+            .lock()
+            .ok()
+            .and_then(|mut cards| cards.pop_front())
+            .unwrap_or(LogicCard::new(0))
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.cards.lock().ok().map(|cards| cards.len())
+    }
+}
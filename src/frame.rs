@@ -1,22 +1,121 @@
-use crate::config::Config;
+use crossterm::style::Color;
+
+use crate::{config::Config, theme::Theme};
+
+/// Colors and attributes for a single cell
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Color,
+    pub bg: Color,
+    pub bold: bool,
+    pub dim: bool,
+    pub reverse: bool,
+}
+
+impl Style {
+    pub const fn new() -> Self {
+        Self {
+            fg: Color::Reset,
+            bg: Color::Reset,
+            bold: false,
+            dim: false,
+            reverse: false,
+        }
+    }
+
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.fg = color;
+        self
+    }
+
+    pub const fn bg(mut self, color: Color) -> Self {
+        self.bg = color;
+        self
+    }
+
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub const fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    pub const fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Do both styles turn on the same attributes?
+    pub fn same_attributes(&self, other: &Style) -> bool {
+        self.bold == other.bold && self.dim == other.dim && self.reverse == other.reverse
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single character on screen along with how to display it
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub style: Style,
+}
+
+impl Cell {
+    pub const fn new(ch: char, style: Style) -> Self {
+        Self { ch, style }
+    }
+
+    /// Placeholder for the column covered by the right half of a wide character.
+    /// It is never printed, the terminal fills it when drawing the character to its left
+    pub const fn continuation(style: Style) -> Self {
+        Self::new('\0', style)
+    }
+
+    pub fn is_continuation(&self) -> bool {
+        self.ch == '\0'
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::new(' ', Style::new())
+    }
+}
 
 // Type alias for the Frame type
-// Vector of vectors of chars
-pub type Frame = Vec<Vec<char>>;
+// Vector of columns of styled cells
+pub type Frame = Vec<Vec<Cell>>;
 
 pub fn new_frame(config: &Config) -> Frame {
     let mut cols = Vec::with_capacity(config.num_cols);
 
     for _ in 0..config.num_cols {
-        // Create vector of num_rows amount of spaces " "
+        // Create vector of num_rows amount of blank cells
         // This will be the dynamic height of the terminal
-        let col = vec![' '; config.num_rows];
+        let col = vec![Cell::default(); config.num_rows];
         cols.push(col);
     }
 
     cols
 }
 
+/// Paint every cell of the frame blank in the given style
+///
+pub fn clear_frame(frame: &mut Frame, style: Style) {
+    for col in frame.iter_mut() {
+        col.fill(Cell::new(' ', style));
+    }
+}
+
 pub trait Drawable {
-    fn draw(&self, frame: &mut Frame);
+    fn draw(&self, frame: &mut Frame, theme: Theme);
 }
@@ -1,38 +1,61 @@
-use crossterm::event::KeyCode;
-
 use crate::{
-    OPPONENT_THINKING_TIME_MS, STAND_THRESHOLD, card::LogicCard, menu::MenuAction, player::{Player, PlayerState}
+    BOT_POLL_MS, EVENT_LOG_SIZE, OPPONENT_THINKING_TIME_MS, ROUNDS_TO_WIN, TARGET_SCORE,
+    bot::Bot,
+    card::LogicCard,
+    controller::{self, Controller},
+    deck::{DeckSource, RandomDeck},
+    player::{Player, PlayerState, Seats},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fmt,
+    time::{Duration, Instant},
 };
-use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Everything that can be done in a game.
+/// Hit, Stand, PlayHand and PlayFlipped are made by a seat on its turn,
+/// the rest by whoever is at the table
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
 pub enum GameAction {
     Hit,
     Stand,
     NextRound,
     NextGame,
     PlayHand { index: usize },
+    PlayFlipped { index: usize }, // a ± side card, counting the other way from its face value
+    TakeTurn,                     // hot-seat: the next player has the keyboard
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum OpponentAction {
-    Hit,
-    Stand,
-    PlayHand { index: usize },
-}
-
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RoundOutcome {
     PlayerWon,
     OpponentWon,
     Tied,
 }
 
+/// Something that happened in a game, kept in a short log for debugging
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEvent {
+    Dealt { player: Player, value: i32 },
+    Played { player: Player, value: i32 },
+    Stood { player: Player },
+    Bust { player: Player },
+    RoundEnded { outcome: RoundOutcome },
+    GameOver { winner: Player },
+    NewRound,
+    NewGame,
+}
+
 #[derive(Debug, Clone)]
 pub enum GamePhase {
-    PlayerTurn,
-    OpponentThinking { until: Instant },
-    OpponentTurn,
+    Turn { seat: Player },                     // waiting on `seat`'s move
+    Thinking { seat: Player, until: Instant }, // the computer pauses before its move
+    Handover { to: Player },                   // hot-seat: hands hidden until `to` has the keyboard
     RoundEnd,
     AwaitingNextRound,
     GameOver { winner: Player },
@@ -40,50 +63,197 @@ pub enum GamePhase {
 
 #[derive(Debug)]
 pub struct GameState {
-    pub player: PlayerState,
-    pub opponent: PlayerState,
+    pub seats: Seats<PlayerState>,
+    pub controllers: Seats<Controller>,
     pub game_phase: GamePhase,
     pub round_outcome: Option<RoundOutcome>,
+    pub open_hands: bool, // every hand face up, for spectators allowed to see them
+    first_seat: Player,   // starts every round
+    deck: Box<dyn DeckSource>,
+    events: VecDeque<GameEvent>, // most recent last, at most EVENT_LOG_SIZE
+    new_events: Vec<GameEvent>,  // not yet taken by take_events
 }
 
 impl GameState {
     pub fn new() -> Self {
         Self {
-            player: PlayerState {
-                name: "Your Name".to_string(),
-                dealer_row: vec![],
-                played_row: vec![],
-                hand: vec![
-                    Some(LogicCard { value: 5 }),
-                    Some(LogicCard { value: 3 }),
-                    Some(LogicCard { value: 6 }),
-                    Some(LogicCard { value: 2 }),
-                ],
-                bust: false,
-                stood: false,
-                rounds_won: 0,
-                played_card: false,
-            },
-            opponent: PlayerState {
-                name: "Opponent".to_string(),
-                dealer_row: vec![],
-                played_row: vec![],
-                hand: vec![
-                    Some(LogicCard { value: 2 }),
-                    Some(LogicCard { value: 6 }),
-                    Some(LogicCard { value: 1 }),
-                    Some(LogicCard { value: 4 }),
-                ],
-                bust: false,
-                stood: false,
-                rounds_won: 0,
-                played_card: false,
+            seats: Seats::new(
+                PlayerState {
+                    name: "Your Name".to_string(),
+                    dealer_row: vec![],
+                    played_row: vec![],
+                    hand: vec![
+                        Some(LogicCard::new(5)),
+                        Some(LogicCard::new(3)),
+                        Some(LogicCard::new(6)),
+                        Some(LogicCard::plus_minus(2)),
+                    ],
+                    bust: false,
+                    stood: false,
+                    rounds_won: 0,
+                    played_card: false,
+                },
+                PlayerState {
+                    name: "Opponent".to_string(),
+                    dealer_row: vec![],
+                    played_row: vec![],
+                    hand: vec![
+                        Some(LogicCard::new(2)),
+                        Some(LogicCard::new(6)),
+                        Some(LogicCard::plus_minus(1)),
+                        Some(LogicCard::new(4)),
+                    ],
+                    bust: false,
+                    stood: false,
+                    rounds_won: 0,
+                    played_card: false,
+                },
+            ),
+            controllers: Seats::new(Controller::Human, Controller::Computer),
+            game_phase: GamePhase::Turn {
+                seat: Player::Player,
             },
-            game_phase: GamePhase::PlayerTurn,
             round_outcome: None,
+            open_hands: false,
+            first_seat: Player::Player,
+            deck: Box::new(RandomDeck::new()),
+            events: VecDeque::with_capacity(EVENT_LOG_SIZE),
+            new_events: vec![],
+        }
+    }
+
+    /// Two people taking turns at the same keyboard.
+    /// Starts on a handover so the first player's hand isn't shown before they're ready
+    ///
+    pub fn hot_seat() -> Self {
+        let mut state = Self::new();
+        state.controllers = Seats::new(Controller::Human, Controller::Human);
+        state.seats[Player::Player].name = "Player 1".to_string();
+        state.seats[Player::Opponent].name = "Player 2".to_string();
+        state.game_phase = GamePhase::Handover { to: Player::Player };
+        state
+    }
+
+    /// Build a game that deals from the given deck and has the opponent play
+    /// `opponent_moves` in order before falling back to its normal logic
+    ///
+    pub fn with_script(deck: Box<dyn DeckSource>, opponent_moves: Vec<GameAction>) -> Self {
+        Self {
+            deck,
+            controllers: Seats::new(Controller::Human, Controller::Script(opponent_moves.into())),
+            ..Self::new()
+        }
+    }
+
+    /// Play against an external bot program instead of the built in computer
+    ///
+    pub fn against_bot(bot: Bot) -> Self {
+        let mut state = Self::new();
+        state.seats[Player::Opponent].name = bot.name().to_string();
+        state.controllers[Player::Opponent] = Controller::Bot(bot);
+        state
+    }
+
+    /// One end of a game played over the network: the opponent's moves arrive from the peer.
+    /// `first_seat` starts every round, as the two ends see the table from opposite sides
+    ///
+    pub fn networked(deck: Box<dyn DeckSource>, first_seat: Player) -> Self {
+        Self {
+            controllers: Seats::new(Controller::Human, Controller::Network),
+            game_phase: GamePhase::Turn { seat: first_seat },
+            first_seat,
+            deck,
+            ..Self::new()
+        }
+    }
+
+    /// A networked game watched from the side: nobody here plays, both seats' moves
+    /// arrive from the host. Seats are the host's way round, so Player's seat goes first
+    ///
+    pub fn spectated(deck: Box<dyn DeckSource>) -> Self {
+        Self {
+            controllers: Seats::new(Controller::Network, Controller::Network),
+            ..Self::networked(deck, Player::Player)
+        }
+    }
+
+    /// Deal from `deck` from now on, e.g. once a networked match's shuffle is agreed
+    ///
+    pub fn set_deck(&mut self, deck: Box<dyn DeckSource>) {
+        self.deck = deck;
+    }
+
+    /// Both seats are people sharing this keyboard
+    ///
+    pub fn is_hot_seat(&self) -> bool {
+        self.controllers
+            .iter()
+            .all(|(_, controller)| controller.is_human())
+    }
+
+    /// Nobody at this keyboard plays either seat
+    ///
+    pub fn is_spectated(&self) -> bool {
+        self.controllers
+            .iter()
+            .all(|(_, controller)| !controller.is_human())
+    }
+
+    /// The last few things that happened, oldest first
+    ///
+    pub fn recent_events(&self) -> impl Iterator<Item = &GameEvent> {
+        self.events.iter()
+    }
+
+    /// An event in words, e.g. "Bob stood", for action logs and text-mode play
+    ///
+    pub fn describe(&self, event: &GameEvent) -> String {
+        let name = |seat: Player| &self.seats[seat].name;
+
+        match *event {
+            GameEvent::Dealt { player, value } => format!("{} drew {value}", name(player)),
+            GameEvent::Played { player, value } => format!("{} played {value}", name(player)),
+            GameEvent::Stood { player } => format!("{} stood", name(player)),
+            GameEvent::Bust { player } => format!("{} went bust", name(player)),
+            GameEvent::RoundEnded { outcome } => match outcome {
+                RoundOutcome::PlayerWon => format!("{} won the round", name(Player::Player)),
+                RoundOutcome::OpponentWon => format!("{} won the round", name(Player::Opponent)),
+                RoundOutcome::Tied => "The round was tied".to_string(),
+            },
+            GameEvent::GameOver { winner } => format!("{} won the match", name(winner)),
+            GameEvent::NewRound => "New round".to_string(),
+            GameEvent::NewGame => "New match".to_string(),
         }
     }
 
+    /// Seed of the random deck, so a game can be replayed from a crash report
+    ///
+    pub fn deck_seed(&self) -> Option<[u8; 32]> {
+        self.deck.seed()
+    }
+
+    /// Dealer cards left in the deck, or None if it never runs out
+    ///
+    pub fn deck_remaining(&self) -> Option<usize> {
+        self.deck.remaining()
+    }
+
+    /// Add to the event log, dropping the oldest event once it's full
+    ///
+    fn log(&mut self, event: GameEvent) {
+        if self.events.len() == EVENT_LOG_SIZE {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+        self.new_events.push(event);
+    }
+
+    /// Events since the last call, oldest first, for anything reacting to the game (e.g. sound)
+    ///
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.new_events)
+    }
+
     /// Take the keys from the game loop and hand them it to action_from_key
     ///
     pub fn handle_game_input(&mut self, key: char) -> Option<GameAction> {
@@ -97,67 +267,111 @@ impl GameState {
             '1' | '2' | '3' | '4' => Some(GameAction::PlayHand {
                 index: key.to_digit(10)? as usize - 1,
             }),
+            // Shift with the card's number
+            '!' | '@' | '#' | '$' => Some(GameAction::PlayFlipped {
+                index: "!@#$".find(key)?,
+            }),
             'd' => Some(GameAction::Hit),
             's' => Some(GameAction::Stand),
             'n' => Some(GameAction::NextRound),
             'g' => Some(GameAction::NextGame),
+            'c' => Some(GameAction::TakeTurn),
             _ => None,
         }
     }
 
-    /// Centralize action validation
+    /// Centralize action validation.
+    /// Moves go to whichever seat has the keyboard, everything else applies to the whole table.
+    /// Returns whether the action was allowed
     ///
-    pub fn apply_game_action(&mut self, action: GameAction) {
+    pub fn apply_game_action(&mut self, action: GameAction) -> bool {
         match action {
-            GameAction::Hit => {
-                if matches!(self.game_phase, GamePhase::PlayerTurn) && !self.player.stood {
-                    self.player_hit();
-                    self.resolve_after_action();
-                }
-            }
-            GameAction::Stand => {
-                if matches!(self.game_phase, GamePhase::PlayerTurn) {
-                    self.player_stand();
-                    self.resolve_after_action();
-                }
-            }
-            GameAction::PlayHand { index } => {
-                if matches!(self.game_phase, GamePhase::PlayerTurn) {
-                    self.play_card(index);
-                    self.resolve_after_action();
-                }
+            GameAction::Hit
+            | GameAction::Stand
+            | GameAction::PlayHand { .. }
+            | GameAction::PlayFlipped { .. } => self
+                .active_seat()
+                .is_some_and(|seat| self.apply_seat_action(seat, action)),
+            GameAction::TakeTurn => {
+                let GamePhase::Handover { to } = self.game_phase else {
+                    return false;
+                };
+                self.game_phase = GamePhase::Turn { seat: to };
+                true
             }
             GameAction::NextRound => {
-                if matches!(self.game_phase, GamePhase::AwaitingNextRound) {
-                    self.next_round();
-                    self.resolve_after_action();
+                if !matches!(self.game_phase, GamePhase::AwaitingNextRound) {
+                    return false;
                 }
+                self.next_round();
+                self.resolve_after_action();
+                true
             }
             GameAction::NextGame => {
-                if matches!(self.game_phase, GamePhase::GameOver { winner }) {
-                    self.new_game();
+                if !matches!(self.game_phase, GamePhase::GameOver { .. }) {
+                    return false;
                 }
+                self.new_game();
+                true
             }
         }
     }
 
-    /// Take an OpponentAction and perform the action by calling appropriate fn's
+    /// `seat` gives up the match, which the other seat wins.
+    /// Returns false if the match was already over
     ///
-    pub fn apply_opponent_action(&mut self, action: OpponentAction) {
+    pub fn resign(&mut self, seat: Player) -> bool {
+        if matches!(self.game_phase, GamePhase::GameOver { .. }) {
+            return false;
+        }
+
+        let winner = seat.other();
+        self.game_phase = GamePhase::GameOver { winner };
+        self.log(GameEvent::GameOver { winner });
+        true
+    }
+
+    /// Make a move for `seat`, if it's their turn and the move is allowed.
+    /// Hitting or standing passes the turn on, side cards can be played first.
+    /// Returns whether the move was made
+    ///
+    pub fn apply_seat_action(&mut self, seat: Player, action: GameAction) -> bool {
+        if !matches!(self.game_phase, GamePhase::Turn { seat: turn } if turn == seat) {
+            return false;
+        }
+
         match action {
-            OpponentAction::Hit => {
-                self.opponent_hit();
-                self.resolve_after_action();
+            GameAction::Hit if !self.seats[seat].stood => {
+                self.hit(seat);
+                self.end_turn(seat);
             }
-            OpponentAction::Stand => {
-                self.opponent_stand();
-                self.resolve_after_action();
+            GameAction::Stand => {
+                self.stand(seat);
+                self.end_turn(seat);
             }
-            OpponentAction::PlayHand { index } => {
-                self.opponent_play_card(index);
-                self.resolve_after_action();
+            GameAction::PlayHand { index }
+                if self.seats[seat]
+                    .hand
+                    .get(index)
+                    .is_some_and(Option::is_some) =>
+            {
+                self.play_card(seat, index, false);
+                self.seats[seat].played_card = true;
+            }
+            GameAction::PlayFlipped { index }
+                if self.seats[seat]
+                    .hand
+                    .get(index)
+                    .is_some_and(|card| card.is_some_and(|card| card.flippable)) =>
+            {
+                self.play_card(seat, index, true);
+                self.seats[seat].played_card = true;
             }
+            _ => return false,
         }
+
+        self.resolve_after_action();
+        true
     }
 
     /// After each state mutation action, check scores to see if status or
@@ -169,45 +383,29 @@ impl GameState {
             return;
         }
 
-        // If player has played a card, move to Opponent's turn and reset flag
-        if self.player.played_card {
-            self.game_phase = GamePhase::OpponentThinking {
-                until: Instant::now() + Duration::from_millis(OPPONENT_THINKING_TIME_MS),
-            };
-            self.player.played_card = false;
-        }
-
-        let player_score = self.player.score();
-        let opponent_score = self.opponent.score();
-
         // Check for bust
         //
-        // If player busts, round ends
-        if player_score > 20 {
-            self.player.bust = true;
-            self.game_phase = GamePhase::RoundEnd;
-            return;
-        }
-
-        // If opponent busts, round ends
-        if opponent_score > 20 {
-            self.opponent.bust = true;
-            self.game_phase = GamePhase::RoundEnd;
-            return;
-        }
-
-        // If player is at 20, stand
-        if player_score == 20 {
-            self.player.stood = true
+        // If either seat busts, round ends
+        for seat in Player::ALL {
+            if self.seats[seat].score() > TARGET_SCORE {
+                if !self.seats[seat].bust {
+                    self.log(GameEvent::Bust { player: seat });
+                }
+                self.seats[seat].bust = true;
+                self.game_phase = GamePhase::RoundEnd;
+                return;
+            }
         }
 
-        // If opponent at 20, stand
-        if opponent_score == 20 {
-            self.opponent.stood = true;
+        // If a seat is at 20, stand
+        for seat in Player::ALL {
+            if self.seats[seat].score() == TARGET_SCORE {
+                self.seats[seat].stood = true;
+            }
         }
 
         // Check if both players have stood
-        if self.player.stood && self.opponent.stood {
+        if self.seats.iter().all(|(_, side)| side.stood) {
             self.game_phase = GamePhase::RoundEnd;
         }
     }
@@ -216,13 +414,15 @@ impl GameState {
     /// transitioning into AwaitingNextRound phase.
     ///
     fn finalize_round(&mut self) {
-        let player_score = self.player.score();
-        let opponent_score = self.opponent.score();
+        let player = &self.seats[Player::Player];
+        let opponent = &self.seats[Player::Opponent];
+        let player_score = player.score();
+        let opponent_score = opponent.score();
 
         // Check scores and decide round outcome
-        let outcome = if self.player.bust {
+        let outcome = if player.bust {
             RoundOutcome::OpponentWon
-        } else if self.opponent.bust || player_score > opponent_score {
+        } else if opponent.bust || player_score > opponent_score {
             RoundOutcome::PlayerWon
         } else if opponent_score > player_score {
             RoundOutcome::OpponentWon
@@ -233,18 +433,18 @@ impl GameState {
         // Apply reward outcome (increment rounds won or not if tied)
         self.round_outcome = Some(outcome);
         self.apply_reward(outcome);
+        self.log(GameEvent::RoundEnded { outcome });
 
         // Check for game win else we move into AwaitingNextRound
-        if self.player.rounds_won == 3 {
-            self.game_phase = GamePhase::GameOver {
-                winner: Player::Player,
+        match Player::ALL
+            .into_iter()
+            .find(|&seat| self.seats[seat].rounds_won == ROUNDS_TO_WIN)
+        {
+            Some(winner) => {
+                self.game_phase = GamePhase::GameOver { winner };
+                self.log(GameEvent::GameOver { winner });
             }
-        } else if self.opponent.rounds_won == 3 {
-            self.game_phase = GamePhase::GameOver {
-                winner: Player::Opponent,
-            }
-        } else {
-            self.game_phase = GamePhase::AwaitingNextRound;
+            None => self.game_phase = GamePhase::AwaitingNextRound,
         }
     }
 
@@ -253,209 +453,217 @@ impl GameState {
     fn apply_reward(&mut self, outcome: RoundOutcome) {
         match outcome {
             RoundOutcome::OpponentWon => {
-                self.opponent.rounds_won += 1;
+                self.seats[Player::Opponent].rounds_won += 1;
             }
             RoundOutcome::PlayerWon => {
-                self.player.rounds_won += 1;
+                self.seats[Player::Player].rounds_won += 1;
             }
             RoundOutcome::Tied => {}
         }
     }
 
-    /// Check the GamePhase each tick of the gameloop and take appropriate actions
+    /// Check the GamePhase each tick of the gameloop and take appropriate actions.
+    /// Returns whether anything changed
     ///
-    pub fn update(&mut self) {
+    pub fn update(&mut self) -> bool {
         match self.game_phase {
-            GamePhase::PlayerTurn => {
-                // If player is done for the round, immediately switch back to Opponent
-                if !self.player_can_act() {
-                    self.game_phase = GamePhase::OpponentThinking {
-                        until: Instant::now() + Duration::from_millis(OPPONENT_THINKING_TIME_MS),
-                    };
-                }
+            // If a seat is done for the round, immediately switch to the other
+            GamePhase::Turn { seat } if !self.can_act(seat) => {
+                self.start_turn(seat.other());
             }
-            GamePhase::OpponentThinking { until } => {
-                if Instant::now() >= until {
-                    self.game_phase = GamePhase::OpponentTurn;
-                }
+            GamePhase::Thinking { seat, until }
+                if Instant::now() >= until || self.controllers[seat].has_answered() =>
+            {
+                self.game_phase = GamePhase::Turn { seat };
             }
-            GamePhase::OpponentTurn => {
-                self.play_opponent_turn();
+            GamePhase::Turn { seat } if self.controllers[seat].is_automatic() => {
+                self.play_automatic_turn(seat);
             }
             GamePhase::RoundEnd => {
                 self.finalize_round();
             }
-            _ => {}
+            _ => return false,
         }
-    }
 
-    /// Check if opponent can still play this round
-    ///
-    fn opponent_can_act(&self) -> bool {
-        !self.opponent.stood && !self.opponent.bust
+        true
     }
 
-    /// Check if player can still play this round
+    /// When update next has something to do, or None if it's waiting on someone's move
     ///
-    fn player_can_act(&self) -> bool {
-        !self.player.stood && !self.player.bust
+    pub fn next_deadline(&self) -> Option<Instant> {
+        match self.game_phase {
+            GamePhase::Turn { seat }
+                if !self.can_act(seat) || self.controllers[seat].is_automatic() =>
+            {
+                Some(Instant::now())
+            }
+            // A bot's answer can come at any time
+            GamePhase::Thinking { seat, until } => Some(match self.controllers[seat] {
+                Controller::Bot(_) => {
+                    until.min(Instant::now() + Duration::from_millis(BOT_POLL_MS))
+                }
+                _ => until,
+            }),
+            GamePhase::RoundEnd => Some(Instant::now()),
+            _ => None,
+        }
     }
 
-    /// Deal a card to the player
+    /// Cut the computer's pause before its move short, for front ends with nothing to show
+    /// while it thinks. A bot still gets its time to answer
     ///
-    fn player_hit(&mut self) {
-        let new_dealer_card_val: i32 = rand::random_range(0..=10);
-        self.player.dealer_row.push(LogicCard {
-            value: new_dealer_card_val,
-        });
-
-        // Set gamephase to opponent's turn
-        if self.opponent_can_act() {
-            self.game_phase = GamePhase::OpponentThinking {
-                until: Instant::now() + Duration::from_millis(OPPONENT_THINKING_TIME_MS),
-            };
+    pub fn skip_thinking(&mut self) {
+        if let GamePhase::Thinking { seat, .. } = self.game_phase
+            && !matches!(self.controllers[seat], Controller::Bot(_))
+        {
+            self.game_phase = GamePhase::Turn { seat };
         }
     }
 
-    /// Opponent's play logic:
-    /// Return an OpponentAction based on opponent's hand and state
+    /// The seat whose turn it is, if they're someone at the keyboard
     ///
-    fn decide_opponent_move(&self) -> OpponentAction {
-        let score = self.opponent.score();
-        let target = 20 - score;
-
-        let card_hits_twenty = |card: &LogicCard| -> bool { card.value == target };
-
-        if let Some(index) = self.first_hand_index(card_hits_twenty) {
-            return OpponentAction::PlayHand { index };
+    pub fn active_seat(&self) -> Option<Player> {
+        match self.game_phase {
+            GamePhase::Turn { seat } if self.controllers[seat].is_human() => Some(seat),
+            _ => None,
         }
+    }
 
-        // if score is >= 17, stand
-        if score >= STAND_THRESHOLD as i32 {
-            return OpponentAction::Stand;
+    /// Whether `seat`'s hand should be face up.
+    /// Normally that's any seat played from this keyboard; in hot-seat only whoever's turn it is
+    ///
+    pub fn shows_hand(&self, seat: Player) -> bool {
+        if self.is_hot_seat() {
+            self.active_seat() == Some(seat)
+        } else {
+            self.open_hands || self.controllers[seat].is_human()
         }
-
-        OpponentAction::Hit
     }
 
-    /// Helper to finds first occurrence of card in hand that matches predicate
+    /// Check if `seat` can still play this round
     ///
-    fn first_hand_index<P>(&self, mut pred: P) -> Option<usize>
-    where
-        P: FnMut(&LogicCard) -> bool,
-    {
-        self.opponent
-            .hand
-            .iter()
-            .enumerate()
-            .find_map(|(i, slot)| slot.as_ref().filter(|card| pred(card)).map(|_| i))
+    fn can_act(&self, seat: Player) -> bool {
+        let side = &self.seats[seat];
+        !side.stood && !side.bust
     }
 
-    /// Play the opponent's turn (deal, play card, stand)
+    /// Give `seat` the turn: the computer thinks first, and in hot-seat
+    /// the keyboard is handed over before anything is shown
     ///
-    fn play_opponent_turn(&mut self) {
-        match self.decide_opponent_move() {
-            OpponentAction::Hit => {
-                self.opponent_hit();
-            }
-            OpponentAction::Stand => {
-                self.opponent_stand();
-            }
-            OpponentAction::PlayHand { index } => {
-                self.opponent_play_card(index);
+    fn start_turn(&mut self, seat: Player) {
+        self.game_phase = match self.controllers[seat] {
+            Controller::Human if self.is_hot_seat() => GamePhase::Handover { to: seat },
+            Controller::Human | Controller::Network => GamePhase::Turn { seat },
+            Controller::Computer | Controller::Script(_) => GamePhase::Thinking {
+                seat,
+                until: Instant::now() + Duration::from_millis(OPPONENT_THINKING_TIME_MS),
+            },
+            // Thinks for as long as it takes to answer, up to its timeout
+            Controller::Bot(ref bot) => {
+                bot.ask(self, seat);
+                GamePhase::Thinking {
+                    seat,
+                    until: Instant::now() + bot.timeout(),
+                }
             }
-        }
-
-        self.game_phase = GamePhase::PlayerTurn;
-        self.resolve_after_action();
+        };
     }
 
-    /// Opponent hits (gets dealer card)
+    /// Pass the turn on after `seat` hits or stands.
+    /// If the other side is done for the round a person carries on, the computer thinks again
     ///
-    fn opponent_hit(&mut self) {
-        let new_dealer_card_val: i32 = rand::random_range(0..=10);
-        self.opponent.dealer_row.push(LogicCard {
-            value: new_dealer_card_val,
-        });
+    fn end_turn(&mut self, seat: Player) {
+        self.seats[seat].played_card = false;
+
+        if self.can_act(seat.other()) {
+            self.start_turn(seat.other());
+        } else if self.controllers[seat].is_automatic() {
+            self.start_turn(seat);
+        }
     }
 
-    /// Set gamestate to opponent's turn if we are on the player's turn
+    /// Make the computer's, script's or bot's move for `seat`.
+    /// It gets one move per turn, so a side card ends its turn too
     ///
-    pub fn player_stand(&mut self) {
-        // Only allow if GamePhase is player's turn
-        if let GamePhase::PlayerTurn = self.game_phase {
-            self.player.stood = true;
-
-            if self.opponent_can_act() {
-                self.game_phase = GamePhase::OpponentThinking {
-                    until: Instant::now() + Duration::from_millis(OPPONENT_THINKING_TIME_MS),
-                };
+    fn play_automatic_turn(&mut self, seat: Player) {
+        if let Controller::Bot(bot) = &self.controllers[seat] {
+            // A bot that's slow or answers with a move it can't make stands, which is always allowed
+            let answer = bot.take_answer();
+            if !answer.is_some_and(|action| self.apply_seat_action(seat, action)) {
+                self.apply_seat_action(seat, GameAction::Stand);
             }
+        } else {
+            // Scripted moves (e.g. the tutorial) take priority over the computer's own logic
+            let scripted = match &mut self.controllers[seat] {
+                Controller::Script(moves) => moves.pop_front(),
+                _ => None,
+            };
+            let action = scripted.unwrap_or_else(|| controller::computer_move(self, seat));
+
+            self.apply_seat_action(seat, action);
+        }
+
+        if matches!(self.game_phase, GamePhase::Turn { seat: turn } if turn == seat) {
+            self.end_turn(seat);
         }
     }
 
-    /// Opponent Stands
+    /// Deal a card to `seat`
     ///
-    fn opponent_stand(&mut self) {
-        self.opponent.stood = true;
+    fn hit(&mut self, seat: Player) {
+        let card = self.deck.deal();
+        self.seats[seat].dealer_row.push(card);
+        self.log(GameEvent::Dealt {
+            player: seat,
+            value: card.value,
+        });
     }
 
-    ///  Remove card from player hand and add it to played_row
+    /// `seat` stands, getting no more dealer cards this round
     ///
-    fn play_card(&mut self, index: usize) {
-        // Bounds checking already done before entering this fn
-        let Some(Some(LogicCard { value: _ })) = self.player.hand.get(index) else {
-            return;
-        };
-
-        if index < self.player.hand.len() {
-            // "Remove" the card from the player's hand by setting value to 0
-            let card_to_play = self.player.hand[index];
-            self.player.hand[index] = None;
-            self.player.played_row.push(card_to_play.unwrap());
-        }
+    fn stand(&mut self, seat: Player) {
+        self.seats[seat].stood = true;
+        self.log(GameEvent::Stood { player: seat });
     }
 
-    /// Opponent plays card
+    /// Move a card from `seat`'s hand to their played row, flipping its sign if `flip`
     ///
-    fn opponent_play_card(&mut self, index: usize) {
-        // Bounds checking already done before entering this fn
-        let Some(Some(LogicCard { value: _ })) = self.opponent.hand.get(index) else {
+    fn play_card(&mut self, seat: Player, index: usize, flip: bool) {
+        let Some(mut card) = self.seats[seat].hand.get_mut(index).and_then(Option::take) else {
             return;
         };
-
-        if index < self.opponent.hand.len() {
-            // "Remove" the card from the opponent's hand by setting value to 0
-            let card_to_play = self.opponent.hand[index];
-            self.opponent.hand[index] = None;
-            self.opponent.played_row.push(card_to_play.unwrap());
+        if flip {
+            card.value = -card.value;
         }
+
+        self.seats[seat].played_row.push(card);
+        self.log(GameEvent::Played {
+            player: seat,
+            value: card.value,
+        });
     }
 
     /// Setup for next round.
-    /// Clear the player and opponent's dealer and played rows, and reset flags.
+    /// Clear both seats' dealer and played rows, and reset flags.
     fn setup_next_round(&mut self) {
-        // Clear dealer row for both players
-        self.player.dealer_row = vec![];
-        self.opponent.dealer_row = vec![];
+        for seat in Player::ALL {
+            let side = &mut self.seats[seat];
 
-        // Clear played row for both players
-        self.player.played_row = vec![];
-        self.opponent.played_row = vec![];
+            // Clear dealer and played rows
+            side.dealer_row = vec![];
+            side.played_row = vec![];
 
-        // Reset stood and busted flags
-        self.player.bust = false;
-        self.player.stood = false;
-        self.opponent.bust = false;
-        self.opponent.stood = false;
+            // Reset stood, busted and played flags
+            side.bust = false;
+            side.stood = false;
+            side.played_card = false;
+        }
 
         // Reset round outcome
         self.round_outcome = None;
 
-        // Set GamePhase to player turn
-        self.game_phase = GamePhase::PlayerTurn;
-        // Reset round outcome
-        self.round_outcome = None;
+        // First seat goes first, after a handover in hot-seat
+        self.start_turn(self.first_seat);
     }
 
     /// If in proper game phase, setup next round
@@ -463,6 +671,7 @@ impl GameState {
     fn next_round(&mut self) {
         if let GamePhase::AwaitingNextRound = self.game_phase {
             self.setup_next_round();
+            self.log(GameEvent::NewRound);
         }
     }
 
@@ -470,9 +679,11 @@ impl GameState {
     ///
     fn new_game(&mut self) {
         if let GamePhase::GameOver { winner: _ } = self.game_phase {
-            self.player.rounds_won = 0;
-            self.opponent.rounds_won = 0;
+            for seat in Player::ALL {
+                self.seats[seat].rounds_won = 0;
+            }
             self.setup_next_round();
+            self.log(GameEvent::NewGame);
         }
     }
 }
@@ -482,3 +693,36 @@ impl Default for GameState {
         Self::new()
     }
 }
+
+/// Short names for the debug overlay and crash reports
+///
+impl fmt::Display for GamePhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GamePhase::Turn { seat } => write!(f, "Turn ({seat:?})"),
+            GamePhase::Thinking { seat, until } => {
+                let left = until.saturating_duration_since(Instant::now());
+                write!(f, "Thinking ({seat:?}, {}ms left)", left.as_millis())
+            }
+            GamePhase::Handover { to } => write!(f, "Handover (to {to:?})"),
+            GamePhase::RoundEnd => write!(f, "RoundEnd"),
+            GamePhase::AwaitingNextRound => write!(f, "AwaitingNextRound"),
+            GamePhase::GameOver { winner } => write!(f, "GameOver ({winner:?} won)"),
+        }
+    }
+}
+
+impl fmt::Display for GameEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameEvent::Dealt { player, value } => write!(f, "{player:?} dealt {value}"),
+            GameEvent::Played { player, value } => write!(f, "{player:?} played {value:+}"),
+            GameEvent::Stood { player } => write!(f, "{player:?} stood"),
+            GameEvent::Bust { player } => write!(f, "{player:?} bust"),
+            GameEvent::RoundEnded { outcome } => write!(f, "round ended: {outcome:?}"),
+            GameEvent::GameOver { winner } => write!(f, "game over: {winner:?} won"),
+            GameEvent::NewRound => write!(f, "new round"),
+            GameEvent::NewGame => write!(f, "new game"),
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use crossterm::event::KeyCode;
+use unicode_width::UnicodeWidthChar;
+
+use crate::{
+    CARD_HEIGHT, CARD_WIDTH, H_PAD,
+    canvas::{Canvas, text_width},
+    card::{CardKind, CardSize, CardView},
+    config::Config,
+    frame::{Drawable, Frame},
+    layout::PageRegions,
+    settings::Settings,
+    theme::{Role, Theme},
+};
+
+/// A small row of cards drawn under a page's text to illustrate the rule
+///
+struct Example {
+    kind: CardKind,
+    cards: &'static [&'static str],
+    caption: &'static str,
+}
+
+/// One page of the rules
+///
+struct Page {
+    title: &'static str,
+    paragraphs: &'static [&'static str],
+    example: Option<Example>,
+}
+
+const PAGES: &[Page] = &[
+    Page {
+        title: "The Goal",
+        paragraphs: &[
+            "Kaazap is played against a single opponent. Each round, both of you build a score \
+             out of the cards on your side of the table, trying to get as close to 20 as \
+             possible without going over.",
+            "Whoever ends the round closer to 20 wins it. The first to win 3 rounds wins the match.",
+        ],
+        example: Some(Example {
+            kind: CardKind::Dealer,
+            cards: &["10", "7", "3"],
+            caption: "10 + 7 + 3 = 20, a perfect score",
+        }),
+    },
+    Page {
+        title: "Dealer Cards",
+        paragraphs: &[
+            "On your turn press 'd' to draw a dealer card. Dealer cards are worth up to 10 and \
+             land in the row under your name.",
+            "Dealer cards can't be refused: once drawn, their value is added to your score.",
+        ],
+        example: Some(Example {
+            kind: CardKind::Dealer,
+            cards: &["4", "9", "2"],
+            caption: "Three dealer cards: your score is 15",
+        }),
+    },
+    Page {
+        title: "Side Cards",
+        paragraphs: &[
+            "You also hold four side cards in your hand, shown along the bottom of the screen. \
+             Press '1' to '4' to play the matching card. Its value is added to your score and \
+             the card moves to your played row.",
+            "A yellow ± card counts either way. '1' to '4' play it as plus, hold Shift \
+             ('!' to '$') to play it as minus.",
+            "Side cards are not refilled between rounds, so save them for the moments they \
+             matter. The opponent's hand is hidden and shown as '?'.",
+        ],
+        example: Some(Example {
+            kind: CardKind::PlayerPlus,
+            cards: &["+5", "+3", "+6", "+2"],
+            caption: "A hand of side cards, played with keys 1-4",
+        }),
+    },
+    Page {
+        title: "Standing",
+        paragraphs: &[
+            "When you are happy with your score press 's' to stand. You keep your score and \
+             won't draw any more cards this round.",
+            "Reaching exactly 20 stands for you automatically. Once both players have stood, \
+             the round ends and the scores are compared.",
+        ],
+        example: Some(Example {
+            kind: CardKind::Dealer,
+            cards: &["8", "6", "4"],
+            caption: "On 18 with a 2 in hand? Play it and stand on 20",
+        }),
+    },
+    Page {
+        title: "Busting",
+        paragraphs: &[
+            "If your score goes over 20 you bust and lose the round immediately, no matter \
+             what your opponent has.",
+            "Keep an eye on both scores at the top of the screen before you draw again.",
+        ],
+        example: Some(Example {
+            kind: CardKind::Dealer,
+            cards: &["9", "8", "6"],
+            caption: "9 + 8 + 6 = 23, busted!",
+        }),
+    },
+    Page {
+        title: "Rounds",
+        paragraphs: &[
+            "The higher score wins the round. A tie awards the round to nobody.",
+            "After a round ends press 'n' to deal the next one. When someone has won 3 rounds \
+             the match is over: press 'g' to start a new game.",
+            "Press space to skip card animations, 'x' at any time to return to the menu, or 'q' \
+             to quit.",
+        ],
+        example: None,
+    },
+    Page {
+        title: "Playing Together",
+        paragraphs: &[
+            "In Two Players mode you take turns at the same keyboard. Between turns both hands \
+             are hidden until the next player presses 'c'.",
+            "To play over the network, one of you starts kaazap with --host and the other with \
+             --join and the host's address. Press 'r' to resign a networked match. Anyone else \
+             can watch with --watch and the host's address.",
+            "Press 't' to chat in either mode. Type a message, or the number of a quick emote, \
+             and press Enter to send it or Esc to cancel.",
+        ],
+        example: None,
+    },
+];
+
+#[derive(Debug, Copy, Clone)]
+pub enum HowToPlayAction {
+    NextPage,
+    PreviousPage,
+    Back,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum HowToPlayEvent {
+    Exit,
+}
+
+#[derive(Debug)]
+pub struct HowToPlayState {
+    page: usize,
+}
+
+impl HowToPlayState {
+    pub fn new() -> Self {
+        Self { page: 0 }
+    }
+
+    pub fn handle_input(&mut self, key: KeyCode) -> Option<HowToPlayAction> {
+        self.action_from_key(key)
+    }
+
+    /// Convert a key pressed into an Action
+    ///
+    pub fn action_from_key(&self, key: KeyCode) -> Option<HowToPlayAction> {
+        match key {
+            KeyCode::Right | KeyCode::Enter => Some(HowToPlayAction::NextPage),
+            KeyCode::Left => Some(HowToPlayAction::PreviousPage),
+            KeyCode::Esc | KeyCode::Backspace => Some(HowToPlayAction::Back),
+            KeyCode::Char(c) => match c {
+                'd' | ' ' => Some(HowToPlayAction::NextPage),
+                'a' => Some(HowToPlayAction::PreviousPage),
+                'x' => Some(HowToPlayAction::Back),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    pub fn apply_action(&mut self, action: HowToPlayAction) -> Option<HowToPlayEvent> {
+        match action {
+            HowToPlayAction::NextPage => {
+                if self.page + 1 < PAGES.len() {
+                    self.page += 1;
+                }
+                None
+            }
+            HowToPlayAction::PreviousPage => {
+                self.page = self.page.saturating_sub(1);
+                None
+            }
+            HowToPlayAction::Back => Some(HowToPlayEvent::Exit),
+        }
+    }
+
+    /// Draw Text Helper
+    ///
+    /// Characters that would land outside the frame are dropped
+    fn draw_text(
+        &self,
+        text: &str,
+        x: usize,
+        y: usize,
+        role: Role,
+        theme: Theme,
+        frame: &mut Frame,
+    ) {
+        Canvas::new(frame).text(text, x, y, theme.style(role));
+    }
+
+    /// Draw text horizontally centered on the screen
+    ///
+    fn draw_centered(
+        &self,
+        text: &str,
+        y: usize,
+        role: Role,
+        theme: Theme,
+        frame: &mut Frame,
+        config: &Config,
+    ) {
+        let x = config.num_cols.saturating_sub(text_width(text)) / 2;
+        self.draw_text(text, x, y, role, theme, frame);
+    }
+
+    /// Draw the example cards centered, with their caption underneath
+    ///
+    fn draw_example(
+        &self,
+        example: &Example,
+        y: usize,
+        settings: &Settings,
+        frame: &mut Frame,
+        config: &Config,
+    ) {
+        let theme = settings.theme;
+        let spacing_x = CARD_WIDTH + 1;
+        let row_width = example.cards.len() * spacing_x - 1;
+        let origin_x = config.num_cols.saturating_sub(row_width) / 2;
+
+        for (i, text) in example.cards.iter().enumerate() {
+            CardView {
+                x: origin_x + i * spacing_x,
+                y,
+                text: text.to_string(),
+                kind: Some(example.kind),
+                style: settings.card_style,
+                size: CardSize::Full,
+            }
+            .draw(frame, theme);
+        }
+
+        let caption_y = y + CARD_HEIGHT + 1;
+        self.draw_centered(example.caption, caption_y, Role::Text, theme, frame, config);
+    }
+
+    /// Main draw fn, lays out the current page top to bottom
+    ///
+    pub fn draw(&self, frame: &mut Frame, config: &Config, settings: &Settings) {
+        let theme = settings.theme;
+        let page = &PAGES[self.page];
+        let regions = PageRegions::new(config, H_PAD);
+        let text_width = regions.body.width.max(1);
+
+        let title = page.title.to_uppercase();
+        self.draw_centered(&title, regions.title.y, Role::Title, theme, frame, config);
+
+        // Paragraphs with a blank line between, cut short with "..." if the body can't fit them
+        let mut lines = vec![];
+        for paragraph in page.paragraphs {
+            lines.extend(wrap_text(paragraph, text_width));
+            lines.push(String::new());
+        }
+        if lines.len() > regions.body.height {
+            lines.truncate(regions.body.height);
+            if let Some(last) = lines.last_mut() {
+                *last = "...".to_string();
+            }
+        }
+
+        for (row, line) in lines.iter().enumerate() {
+            let y = regions.body.y + row;
+            self.draw_text(line, regions.body.x, y, Role::Text, theme, frame);
+        }
+
+        // The example and its caption only go in if there's room for all of them
+        let y = regions.body.y + lines.len();
+        if let Some(example) = &page.example
+            && y + CARD_HEIGHT + 2 <= regions.body.bottom()
+        {
+            self.draw_example(example, y, settings, frame, config);
+        }
+
+        // Shortened to fit a narrow terminal rather than cut off
+        let page_number = format!("{} of {}", self.page + 1, PAGES.len());
+        let mut footer = format!("<- a   Page {page_number}   d ->      x: back to menu");
+        if footer.chars().count() > config.num_cols {
+            footer = format!("<- a  {page_number}  d ->  x: menu");
+        }
+        self.draw_centered(&footer, regions.footer.y, Role::Divider, theme, frame, config);
+    }
+}
+
+impl Default for HowToPlayState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Greedy word wrap of `text` into lines no longer than `width` characters.
+/// Words longer than a full line are split across lines.
+///
+pub(crate) fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word = word;
+
+        // Hard split any word that can't fit on a line by itself
+        while text_width(word) > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            let (head, tail) = word.split_at(split_index(word, width));
+            lines.push(head.to_string());
+            word = tail;
+        }
+
+        if !line.is_empty() && text_width(&line) + 1 + text_width(word) > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Byte index ending the longest prefix of `word` that fits in `width` columns,
+/// always taking at least one character so wrapping makes progress
+///
+fn split_index(word: &str, width: usize) -> usize {
+    let mut columns = 0;
+
+    for (i, ch) in word.char_indices() {
+        columns += ch.width().unwrap_or(0);
+        if columns > width && i > 0 {
+            return i;
+        }
+    }
+
+    word.len()
+}
@@ -0,0 +1,184 @@
+use crate::config::Config;
+
+/// A rectangle of cells on screen, `x` and `y` being its top left corner
+///
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// How much space a row or column takes when a rect is split
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Size {
+    Fixed(usize), // exactly this many cells, or whatever is left
+    Fill,         // an equal share of the space left over by fixed sizes
+}
+
+/// Where to place something narrower than the rect it's drawn in
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+}
+
+impl Rect {
+    pub const fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// The whole terminal
+    ///
+    pub fn screen(config: &Config) -> Self {
+        Self::new(0, 0, config.num_cols, config.num_rows)
+    }
+
+    /// One past the rightmost column
+    ///
+    pub fn right(&self) -> usize {
+        self.x + self.width
+    }
+
+    /// One past the bottom row
+    ///
+    pub fn bottom(&self) -> usize {
+        self.y + self.height
+    }
+
+    /// Shrink the rect by `h` columns on the left and right and `v` rows on the top and bottom
+    ///
+    pub fn pad(&self, h: usize, v: usize) -> Rect {
+        Rect::new(
+            self.x + h.min(self.width / 2),
+            self.y + v.min(self.height / 2),
+            self.width.saturating_sub(h * 2),
+            self.height.saturating_sub(v * 2),
+        )
+    }
+
+    /// Stack rows top to bottom, one per size.
+    /// Fixed rows are handed out first, fill rows share what's left
+    /// with any remainder going to the first fill row.
+    ///
+    pub fn split_rows(&self, sizes: &[Size]) -> Vec<Rect> {
+        split(self.height, sizes)
+            .into_iter()
+            .map(|(offset, height)| Rect::new(self.x, self.y + offset, self.width, height))
+            .collect()
+    }
+
+    /// Place columns left to right, one per size, shared out like `split_rows`
+    ///
+    pub fn split_columns(&self, sizes: &[Size]) -> Vec<Rect> {
+        split(self.width, sizes)
+            .into_iter()
+            .map(|(offset, width)| Rect::new(self.x + offset, self.y, width, self.height))
+            .collect()
+    }
+
+    /// Single row at `offset` from the top, empty if it falls outside the rect
+    ///
+    pub fn row(&self, offset: usize) -> Rect {
+        let height = usize::from(offset < self.height);
+        Rect::new(self.x, self.y + offset.min(self.height), self.width, height)
+    }
+
+    /// A `width` x `height` rect aligned inside this one, clamped to fit
+    ///
+    pub fn align(&self, width: usize, height: usize, h: Align, v: Align) -> Rect {
+        let width = width.min(self.width);
+        let height = height.min(self.height);
+
+        Rect::new(
+            self.x + offset(self.width - width, h),
+            self.y + offset(self.height - height, v),
+            width,
+            height,
+        )
+    }
+
+    /// Column to start drawing something `width` wide so it sits aligned in the rect
+    ///
+    pub fn align_x(&self, width: usize, align: Align) -> usize {
+        self.x + offset(self.width.saturating_sub(width), align)
+    }
+}
+
+// Starting offset of something with `free` cells left around it
+fn offset(free: usize, align: Align) -> usize {
+    match align {
+        Align::Start => 0,
+        Align::Center => free / 2,
+        Align::End => free,
+    }
+}
+
+// Share `total` cells out between sizes, returning (offset, length) pairs
+fn split(total: usize, sizes: &[Size]) -> Vec<(usize, usize)> {
+    let fixed: usize = sizes
+        .iter()
+        .map(|size| match size {
+            Size::Fixed(n) => *n,
+            Size::Fill => 0,
+        })
+        .sum();
+    let fills = sizes.iter().filter(|size| **size == Size::Fill).count();
+
+    let left_over = total.saturating_sub(fixed);
+    let share = left_over.checked_div(fills).unwrap_or(0);
+    let mut remainder = left_over - share * fills;
+
+    let mut spans = Vec::with_capacity(sizes.len());
+    let mut offset = 0;
+
+    for size in sizes {
+        let wanted = match size {
+            Size::Fixed(n) => *n,
+            Size::Fill => share + std::mem::take(&mut remainder),
+        };
+        let length = wanted.min(total - offset);
+
+        spans.push((offset, length));
+        offset += length;
+    }
+
+    spans
+}
+
+/// Title, body and footer shared by the full screen pages (how to play, settings)
+///
+pub struct PageRegions {
+    pub title: Rect,
+    pub body: Rect,
+    pub footer: Rect,
+}
+
+impl PageRegions {
+    pub fn new(config: &Config, padding: usize) -> Self {
+        let rows = Rect::screen(config).split_rows(&[
+            Size::Fixed(2),
+            Size::Fixed(1), // title
+            Size::Fixed(1),
+            Size::Fill, // body
+            Size::Fixed(1),
+            Size::Fixed(1), // footer
+            Size::Fixed(1),
+        ]);
+
+        Self {
+            title: rows[1],
+            body: rows[3].pad(padding, 0),
+            footer: rows[5],
+        }
+    }
+}
@@ -1,33 +1,80 @@
 pub mod frame;
+pub mod canvas;
 pub mod player;
 pub mod render;
 pub mod config;
 pub mod game;
+pub mod controller;
+pub mod layout;
+pub mod animation;
 pub mod board;
 pub mod card;
 pub mod screen;
 pub mod menu;
+pub mod how_to_play;
+pub mod deck;
+pub mod tutorial;
+pub mod theme;
+pub mod settings;
+pub mod debug;
+pub mod crash;
+pub mod audio;
+pub mod shuffle;
+pub mod protocol;
+pub mod net;
+pub mod chat;
+pub mod bot;
+pub mod plain;
 pub mod app;
 
 // Card size
 pub const CARD_WIDTH: usize = 9;
 pub const CARD_HEIGHT: usize = 5;
 
-pub const MIN_CARD_SIZE_WIDTH: usize = 7;
-pub const MIN_CARD_SIZE_HEIGHT: usize = 4;
+// Compact card sizes for small terminals
+pub const SMALL_CARD_WIDTH: usize = 5;
+pub const SMALL_CARD_HEIGHT: usize = 3;
+pub const TOKEN_WIDTH: usize = 4; // e.g. "[+6]" or "[10]"
+
+// Number of side cards dealt into each hand
+pub const HAND_SIZE: usize = 4;
 
 // Padding
 pub const H_PAD: usize = 4;
-pub const V_PAD: usize = 4;
+
+// Rules
+pub const TARGET_SCORE: i32 = 20; // over this is bust
+pub const ROUNDS_TO_WIN: usize = 3;
 
 // Opponent Logic
 pub const STAND_THRESHOLD: usize = 17;
 
-// Thread sleep time to keep from wasting cycles
-pub const GAME_LOOP_SLEEP_MS: u64 = 50;
+// Time between frames while cards are animating
+pub const ANIMATION_FRAME_MS: u64 = 33;
 
 // Menu animation time
 pub const MENU_ANIMATION_TIME_MS: u64 = 500;
 
+// Card animation times
+pub const DEAL_ANIMATION_TIME_MS: u64 = 250;
+pub const FLIP_ANIMATION_TIME_MS: u64 = 300;
+
+// Number of recent game events kept for debugging
+pub const EVENT_LOG_SIZE: usize = 32;
+
 // Opponent thinking time
 pub const OPPONENT_THINKING_TIME_MS: u64 = 1000;
+
+// External bots
+pub const BOT_TIMEOUT_MS: u64 = 2000; // how long a bot gets to answer before it stands
+pub const BOT_POLL_MS: u64 = 20; // how often to check whether it has
+
+// Chat
+pub const CHAT_MAX_LEN: usize = 60; // characters in one message
+pub const CHAT_LINES: usize = 3; // most recent messages shown on the board
+pub const EMOTES: [&str; 5] = ["Nice hand", "Ouch", "Good game", "Well played", "Your move"];
+
+// Networked games
+pub const DEFAULT_PORT: u16 = 4720;
+pub const NET_POLL_MS: u64 = 50; // how often to check for messages from the other end
+pub const RECONNECT_WINDOW_SECS: u64 = 60; // how long a dropped game waits for the peer to return
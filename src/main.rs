@@ -1,82 +1,275 @@
-use crossterm::{
-    ExecutableCommand,
-    cursor::{Hide, Show},
-    event::{self, Event, KeyCode},
-    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use anyhow::{Context, anyhow, bail};
+use crossterm::event::{self, Event, KeyCode};
 use kaazap::{
-    GAME_LOOP_SLEEP_MS,
+    DEFAULT_PORT,
     app::App,
+    audio::{self, AudioBackend, Cue, RustyAudioBackend, SilentBackend},
+    bot::Bot,
     config::Config,
+    crash::{self, TerminalGuard},
     frame::{self, new_frame},
-    render,
+    game::GameState,
+    net::NetSession,
+    plain,
+    player::Player,
+    render::{RenderStats, RenderTarget, Terminal},
+    settings::Settings,
 };
 use std::{
-    io,
+    env,
+    io::{self, BufReader},
+    net::{TcpListener, TcpStream},
     sync::mpsc,
     thread,
     time::{Duration, Instant},
 };
-// use rusty_audio::Audio;
+
+const USAGE: &str = "usage: kaazap [--no-sound] [--host [PORT] [--show-hands SECONDS] | \
+                     --join ADDRESS[:PORT] | --watch ADDRESS[:PORT]] [--name NAME] \
+                     [--bot COMMAND] [--plain]";
+
+// Command line options
+struct Options {
+    sound: bool,
+    network: Option<Network>,
+    name: Option<String>,
+    show_hands: Option<Duration>, // host: how far behind the game spectators see both hands
+    bot: Option<String>,          // program to play against instead of the computer
+    plain: bool,                  // text-mode play on stdin and stdout instead of the board
+}
+
+// Which end of a networked game to play, or to watch it
+enum Network {
+    Host { port: u16 },
+    Join { address: String },
+    Watch { address: String },
+}
+
+fn parse_args() -> anyhow::Result<Options> {
+    let mut options = Options {
+        sound: true,
+        network: None,
+        name: None,
+        show_hands: None,
+        bot: None,
+        plain: false,
+    };
+
+    let mut args = env::args().skip(1).peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--no-sound" => options.sound = false,
+            "--plain" => options.plain = true,
+            "--host" => {
+                // The port is optional, so only take the next argument if it isn't an option
+                let port = match args.next_if(|next| !next.starts_with('-')) {
+                    Some(port) => port.parse().with_context(|| format!("bad port {port}"))?,
+                    None => DEFAULT_PORT,
+                };
+                options.network = Some(Network::Host { port });
+            }
+            "--join" | "--watch" => {
+                let Some(address) = args.next() else {
+                    bail!("{arg} needs an address\n{USAGE}");
+                };
+                // Assume the default port if none was given
+                let address = if address.contains(':') {
+                    address
+                } else {
+                    format!("{address}:{DEFAULT_PORT}")
+                };
+                options.network = Some(if arg == "--join" {
+                    Network::Join { address }
+                } else {
+                    Network::Watch { address }
+                });
+            }
+            "--show-hands" => {
+                let Some(seconds) = args.next() else {
+                    bail!("--show-hands needs a number of seconds\n{USAGE}");
+                };
+                let seconds = seconds
+                    .parse()
+                    .with_context(|| format!("bad number of seconds {seconds}"))?;
+                options.show_hands = Some(Duration::from_secs(seconds));
+            }
+            "--name" => {
+                let Some(name) = args.next() else {
+                    bail!("--name needs a name\n{USAGE}");
+                };
+                options.name = Some(name);
+            }
+            "--bot" => {
+                let Some(command) = args.next() else {
+                    bail!("--bot needs a command to run\n{USAGE}");
+                };
+                options.bot = Some(command);
+            }
+            _ => bail!("unknown argument {arg}\n{USAGE}"),
+        }
+    }
+
+    Ok(options)
+}
+
+// Start listening or connect for a networked game, before the terminal is taken over
+// so any problem is reported plainly
+fn connect(network: &Network, options: &Options) -> anyhow::Result<NetSession> {
+    let name = options.name.clone();
+    let connect_to = |address: &String| {
+        TcpStream::connect(address).with_context(|| format!("couldn't connect to {address}"))
+    };
+
+    let session = match network {
+        Network::Host { port } => {
+            let listener = TcpListener::bind(("0.0.0.0", *port))
+                .with_context(|| format!("couldn't listen on port {port}"))?;
+            let name = name.unwrap_or_else(|| "Host".to_string());
+            let mut session = NetSession::host(listener, &name)?;
+            session.spectator_hands = options.show_hands;
+            session
+        }
+        Network::Join { address } => {
+            let name = name.unwrap_or_else(|| "Guest".to_string());
+            NetSession::join(connect_to(address)?, &name)?
+        }
+        Network::Watch { address } => {
+            let name = name.unwrap_or_else(|| "Spectator".to_string());
+            NetSession::watch(connect_to(address)?, &name)?
+        }
+    };
+
+    Ok(session)
+}
+
+// Text-mode play against the computer, a bot or over the network,
+// with no sound and the terminal left alone
+fn play_plain(options: &Options) -> anyhow::Result<()> {
+    if let Some(network) = &options.network {
+        let session = connect(network, options)?;
+        plain::run_online(session, BufReader::new(io::stdin()), io::stdout().lock())?;
+        return Ok(());
+    }
+
+    let mut game_state = match &options.bot {
+        Some(command) => GameState::against_bot(
+            Bot::spawn(command).with_context(|| format!("couldn't start bot {command}"))?,
+        ),
+        None => GameState::new(),
+    };
+    game_state.seats[Player::Player].name =
+        options.name.clone().unwrap_or_else(|| "You".to_string());
+
+    plain::run(game_state, io::stdin().lock(), io::stdout().lock())?;
+    Ok(())
+}
 
 fn main() -> anyhow::Result<()> {
+    let options = parse_args()?;
+    if options.plain {
+        return play_plain(&options);
+    }
+    let session = match &options.network {
+        Some(network) => Some(connect(network, &options)?),
+        None => None,
+    };
+    // Started up front too, so a bot that won't run is reported plainly
+    let bot = match &options.bot {
+        Some(command) => {
+            Some(Bot::spawn(command).with_context(|| format!("couldn't start bot {command}"))?)
+        }
+        None => None,
+    };
 
-    // TODO: Setup Audio
-    // let mut audio = Audio::new();
-    // audio.add("startup", "startup.wav");
-    // audio.play("startup");
+    // Audio
+    //
+    // Any sound that can't be loaded just doesn't play
+    let mut audio: Box<dyn AudioBackend> = if options.sound {
+        Box::new(RustyAudioBackend::load(&audio::assets_dir()))
+    } else {
+        Box::new(SilentBackend)
+    };
+    audio.play(Cue::Startup);
 
     // Terminal Initialization
-    let config = Config::from_terminal()?;
-    let mut stdout = io::stdout();
-    terminal::enable_raw_mode()?;
-    stdout.execute(EnterAlternateScreen)?;
-    stdout.execute(Hide)?; // Hide cursor
+    //
+    // The guard puts the terminal back however main exits,
+    // and the panic hook does the same for a panic in any thread
+    let mut config = Config::from_terminal()?;
+    crash::install_panic_hook();
+    let terminal_guard = TerminalGuard::enter()?;
 
     // Initialize app
-    let mut app = App::new(config.clone());
+    let mut app = App::with_audio(config.clone(), Settings::new(), audio);
+    if let Some(bot) = bot {
+        app.use_bot(bot);
+    }
+    // A networked game skips the menu
+    if let Some(session) = session {
+        app.start_online(session);
+    }
 
     // Initialize time for animations
     let mut last_frame_time = Instant::now();
 
     // Render Loop
     //
-    // Use separate thread for rendering
-    let (render_tx, render_rx) = mpsc::sync_channel(1);
+    // Use separate thread for rendering.
+    // Frames are sent along with whether they must be fully re-rendered (e.g. after a resize)
+    let (render_tx, render_rx) = mpsc::sync_channel::<(frame::Frame, bool)>(1);
+    // What each render took goes back to the app for the debug overlay
+    let (stats_tx, stats_rx) = mpsc::channel::<RenderStats>();
     let render_config = config.clone();
     let render_handle = thread::spawn(move || {
         let mut last_frame = frame::new_frame(&render_config);
-        let mut stdout = io::stdout();
+        let mut terminal = Terminal::new(io::stdout());
         // first frame so we need to force render and last frame is what we have
-        render::render(&mut stdout, &last_frame, &last_frame, true);
+        terminal.render(&last_frame, &last_frame, true).unwrap();
 
         // incremental updates
-        while let Ok(mut curr_frame) = render_rx.recv() {
-            // Drain queued frames (only keep the most current)
-            while let Ok(newer) = render_rx.try_recv() {
+        while let Ok((mut curr_frame, mut force)) = render_rx.recv() {
+            // Drain queued frames (only keep the most current, but remember any forced render)
+            while let Ok((newer, newer_force)) = render_rx.try_recv() {
                 curr_frame = newer;
+                force |= newer_force;
             }
             // Now we're ready to render our frame
-            render::render(&mut stdout, &last_frame, &curr_frame, false);
+            let stats = terminal.render(&last_frame, &curr_frame, force).unwrap();
+            let _ = stats_tx.send(stats);
             last_frame = curr_frame;
         }
     });
 
+    // Set when the next frame must be fully re-rendered
+    let mut force_render = false;
+
     // Game loop
     //
+    // Sleeps until there's input or the app has something scheduled (an animation frame,
+    // the opponent finishing their think), and only draws when something changed
     'gameloop: loop {
-        let mut curr_frame = new_frame(&config);
-
         // Input handling:
         //
-        // Poll for input events with default input,
-        // which returns immediately if nothing to act upon
-        if event::poll(Duration::from_millis(0))?
-            && let Event::Key(key_event) = event::read()?
-        {
-            match key_event.code {
-                KeyCode::Char('q') => break 'gameloop,
-                _ => app.handle_key(key_event.code),
+        // Wait for input until the app's next deadline,
+        // or for as long as it takes if nothing is scheduled
+        let has_event = match app.next_deadline() {
+            Some(deadline) => event::poll(deadline.saturating_duration_since(Instant::now()))?,
+            None => true,
+        };
+
+        if has_event {
+            match event::read()? {
+                Event::Key(key_event) => match key_event.code {
+                    KeyCode::Char('q') if !app.is_typing() => break 'gameloop,
+                    _ => app.handle_key(key_event.code),
+                },
+                // Rebuild everything that depends on the terminal size
+                Event::Resize(cols, rows) => {
+                    config = Config::from_size(cols, rows);
+                    app.resize(config.clone());
+                    force_render = true;
+                }
+                _ => {}
             }
         }
 
@@ -89,28 +282,40 @@ fn main() -> anyhow::Result<()> {
         app.tick(dt);
         last_frame_time = now;
 
+        // Pick up stats for frames rendered since, shown next time the app draws
+        while let Ok(stats) = stats_rx.try_recv() {
+            app.record_render(stats);
+        }
+
         // Draw and render section
+        if !app.is_dirty() && !force_render {
+            continue;
+        }
+
+        let mut curr_frame = new_frame(&config);
         app.draw(&mut curr_frame);
+        crash::set_context(app.crash_context());
 
         // Send the frame!
-        // Ignore the result since the receiving end of the channel won't be ready for a while
-        let _ = render_tx.try_send(curr_frame);
-        // Sleep since our game loop is much faster than the render loop
-        thread::sleep(Duration::from_millis(GAME_LOOP_SLEEP_MS));
+        // Blocks only until the render thread has taken the previous one
+        if render_tx.send((curr_frame, force_render)).is_err() {
+            break 'gameloop;
+        }
+        force_render = false;
     }
 
     // Cleanup and close
     //
     // First make sure threads are cleaned up
     drop(render_tx);
-    render_handle.join().unwrap();
+    // The panic hook has already reported a render thread panic
+    let render_result = render_handle
+        .join()
+        .map_err(|_| anyhow!("render thread panicked"));
 
-    // TODO: Cleanup audio once implemented
-    // audio.wait(); // wait for audio to finish so it isn't cut off
+    app.wait_for_audio(); // wait for audio to finish so it isn't cut off
 
-    stdout.execute(Show)?; // Re-show the cursor (since hidden in alternate screen)
-    stdout.execute(LeaveAlternateScreen)?;
-    terminal::disable_raw_mode()?;
+    drop(terminal_guard); // Re-show the cursor and leave the alternate screen
 
-    Ok(())
+    render_result
 }
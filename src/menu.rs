@@ -4,17 +4,28 @@ use strum_macros::EnumIter;
 
 use std::{fmt, time::Duration};
 
-use crate::{MENU_ANIMATION_TIME_MS, config::Config, frame::Frame};
+use crate::{
+    MENU_ANIMATION_TIME_MS,
+    canvas::{Canvas, text_width},
+    config::Config,
+    frame::Frame,
+    layout::{Align, Rect, Size},
+    theme::{Role, Theme},
+};
 
 #[derive(EnumIter, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MenuItem {
     StartGame,
+    HotSeat,
+    Tutorial,
     HowToPlay,
+    Settings,
 }
 
 #[derive(Debug, Copy, Clone)]
 pub enum MenuEvent {
     Activate { menu_item: MenuItem },
+    Moved, // selection went up or down
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -47,79 +58,147 @@ impl MenuState {
 
     /// Draw Text Helper
     ///
-    /// Takes the text to draw, location coords and frame to draw into
-    fn draw_text(&self, text: &str, x: usize, y: usize, frame: &mut Frame) {
-        for (i, ch) in text.chars().enumerate() {
-            frame[x + i][y] = ch;
-        }
+    /// Takes the text to draw, location coords, the theme role to style it with and frame to draw into.
+    /// Characters that would land outside the frame are dropped
+    fn draw_text(
+        &self,
+        text: &str,
+        x: usize,
+        y: usize,
+        role: Role,
+        theme: Theme,
+        frame: &mut Frame,
+    ) {
+        Canvas::new(frame).text(text, x, y, theme.style(role));
     }
 
     /// Draw the title which is a Vector<&'static str>
     ///
-    /// Iterate through each line and send it to draw_text
-    fn draw_title(&self, x: usize, y: usize, frame: &mut Frame) {
+    /// Iterate through each line and send it to draw_text, keeping the art's lines lined up
+    fn draw_title(&self, area: Rect, theme: Theme, frame: &mut Frame) {
+        let x = area.align_x(self.title_width(), Align::Center);
         for (row, line) in self.title_text.iter().enumerate() {
-            self.draw_text(line, x, y + row, frame);
+            self.draw_text(line, x, area.y + row, Role::Title, theme, frame);
         }
     }
 
-    fn draw_menu_items(&self, x: usize, y: usize, frame: &mut Frame) {
-        let mut padding_y = y + 15;
-
-        for menu_item in MenuItem::iter() {
-            let menu_item_text = menu_item.to_string();
-            padding_y += 2;
+    /// Width of the widest line of the title art, in characters
+    ///
+    fn title_width(&self) -> usize {
+        self.title_text
+            .iter()
+            .map(|line| text_width(line))
+            .max()
+            .unwrap_or(0)
+    }
 
+    /// Draw the menu items centered in the area, with a blank line between each
+    ///
+    fn draw_menu_items(&self, area: Rect, spacing: usize, theme: Theme, frame: &mut Frame) {
+        for (i, menu_item) in MenuItem::iter().enumerate() {
             // If this is the selected item, draw an annotation
-            if self.selected == menu_item {
-                match self.animation_state {
-                    true => {
-                        let selected_text = format!("-- {} --", menu_item_text);
-                        let padding_x = x - 2 - selected_text.len() / 2;
-                        self.draw_text(&selected_text, padding_x, padding_y, frame);
-                    }
-                    false => {
-                        let selected_text = format!("++ {} ++", menu_item_text);
-                        let padding_x = x - 2 - selected_text.len() / 2;
-                        self.draw_text(&selected_text, padding_x, padding_y, frame);
-                    }
-                }
+            let (text, role) = if self.selected == menu_item {
+                let selected_text = match self.animation_state {
+                    true => format!("-- {} --", menu_item),
+                    false => format!("++ {} ++", menu_item),
+                };
+                (selected_text, Role::Highlight)
             } else {
-                let padding_x = x - 2 - menu_item_text.len() / 2;
-                self.draw_text(&menu_item_text, padding_x, padding_y, frame);
-            }
+                (menu_item.to_string(), Role::Text)
+            };
+
+            let x = area.align_x(text_width(&text), Align::Center);
+            self.draw_text(&text, x, area.y + i * spacing, role, theme, frame);
         }
     }
 
-    /// Main draw fn figures out where to render each element, then sends it out
+    /// Main draw fn lays out the title and menu items, then sends them out
     ///
-    pub fn draw(&self, frame: &mut Frame, config: &Config) {
-        // TODO: stop using magic numbers for positioning
-        let mid = config.num_cols / 2;
-        let padding_x = self.title_text[1].len() / 2 - 19;
-        let padding_y = 5;
+    pub fn draw(&self, frame: &mut Frame, config: &Config, theme: Theme) {
+        let screen = Rect::screen(config);
+        let item_count = MenuItem::iter().count();
+        // Items have a blank line between them unless that's what stops them fitting
+        let items_height = |spacing: usize| (item_count - 1) * spacing + 1;
+
+        for spacing in [2, 1] {
+            let art = screen.split_rows(&[
+                Size::Fixed(5),
+                Size::Fixed(self.title_text.len()),
+                Size::Fixed(3),
+                Size::Fixed(items_height(spacing)),
+                Size::Fill,
+            ]);
+
+            let art_fits = self.title_width() < config.num_cols && art[4].height > 0;
+            if art_fits {
+                self.draw_title(art[1], theme, frame);
+                self.draw_menu_items(art[3], spacing, theme, frame);
+                return;
+            }
+        }
 
-        self.draw_title(mid - padding_x, padding_y, frame);
+        // Fall back to a one line title when the art doesn't fit, e.g. in a split pane
+        let spacing = if items_height(2) + 4 <= screen.height {
+            2
+        } else {
+            1
+        };
+        let rows = screen.split_rows(&[
+            Size::Fixed(1),
+            Size::Fixed(1),
+            Size::Fixed(2),
+            Size::Fixed(items_height(spacing)),
+        ]);
 
-        self.draw_menu_items(mid, padding_y, frame);
+        let title = "K A A Z A P";
+        let x = rows[1].align_x(text_width(title), Align::Center);
+        self.draw_text(title, x, rows[1].y, Role::Title, theme, frame);
+        self.draw_menu_items(rows[3], spacing, theme, frame);
     }
 
-    /// Accumulate time up to duration to drive menu animations
+    /// Accumulate time up to duration to drive menu animations.
+    /// Returns whether the animation moved on and the menu needs redrawing
     ///
-    pub fn tick(&mut self, dt: Duration) {
+    pub fn tick(&mut self, dt: Duration) -> bool {
         self.time_accumulated += dt;
         if self.time_accumulated >= Duration::from_millis(MENU_ANIMATION_TIME_MS) {
             // toggle anim status
             self.animation_state = !self.animation_state;
             self.time_accumulated -= Duration::from_millis(MENU_ANIMATION_TIME_MS);
+            return true;
         }
+
+        false
     }
 
-    pub fn toggle_selected(&mut self) -> MenuItem {
-        match self.selected {
-            MenuItem::StartGame => self.selected = MenuItem::HowToPlay,
-            MenuItem::HowToPlay => self.selected = MenuItem::StartGame,
-        }
+    /// Time until the animation next toggles
+    ///
+    pub fn time_to_next_frame(&self) -> Duration {
+        Duration::from_millis(MENU_ANIMATION_TIME_MS).saturating_sub(self.time_accumulated)
+    }
+
+    /// Move the selection down one item, wrapping around to the top
+    ///
+    pub fn select_next(&mut self) -> MenuItem {
+        let items: Vec<MenuItem> = MenuItem::iter().collect();
+        let index = items
+            .iter()
+            .position(|item| *item == self.selected)
+            .unwrap_or(0);
+        self.selected = items[(index + 1) % items.len()];
+
+        self.selected
+    }
+
+    /// Move the selection up one item, wrapping around to the bottom
+    ///
+    pub fn select_previous(&mut self) -> MenuItem {
+        let items: Vec<MenuItem> = MenuItem::iter().collect();
+        let index = items
+            .iter()
+            .position(|item| *item == self.selected)
+            .unwrap_or(0);
+        self.selected = items[(index + items.len() - 1) % items.len()];
 
         self.selected
     }
@@ -151,12 +230,12 @@ impl MenuState {
                 menu_item: self.selected,
             }),
             MenuAction::SelectionDown => {
-                self.toggle_selected();
-                None
+                self.select_next();
+                Some(MenuEvent::Moved)
             }
             MenuAction::SelectionUp => {
-                self.toggle_selected();
-                None
+                self.select_previous();
+                Some(MenuEvent::Moved)
             }
         }
     }
@@ -174,7 +253,10 @@ impl fmt::Display for MenuItem {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             MenuItem::StartGame => write!(f, "Start Game"),
+            MenuItem::HotSeat => write!(f, "Two Players"),
+            MenuItem::Tutorial => write!(f, "Tutorial"),
             MenuItem::HowToPlay => write!(f, "How To Play"),
+            MenuItem::Settings => write!(f, "Settings"),
         }
     }
 }
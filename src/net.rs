@@ -0,0 +1,1380 @@
+// Playing over TCP: one end hosts and the other joins.
+//
+// The host is authoritative. It deals every card and applies every move, then sends each
+// action on with the card involved and a hash of the table. A client never changes its
+// game itself: its moves go to the host and take effect when the host sends them back.
+// If the connection drops mid game the host keeps the game for RECONNECT_WINDOW_SECS,
+// replaying it to the peer when they return, while the client keeps trying to get back.
+// Each match is dealt from a shuffle both ends contribute to, see shuffle.rs.
+//
+// Anyone else who connects can watch. A spectator is sent a snapshot of the table, then
+// every action the host applies, and never has a say in the game.
+
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead, BufReader, ErrorKind, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::mpsc::{self, Receiver, SyncSender, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    NET_POLL_MS, RECONNECT_WINDOW_SECS, ROUNDS_TO_WIN, TARGET_SCORE,
+    card::LogicCard,
+    chat,
+    deck::{RandomDeck, RelayedDeck},
+    game::{GameAction, GamePhase, GameState, RoundOutcome},
+    player::Player,
+    protocol::{Message, PROTOCOL_VERSION, SeatSnapshot, TablePhase, table_hash},
+    shuffle::{self, Seed},
+};
+
+// How often a client tries to get back to a host it lost
+const RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+// Number of actions in a spectator's log
+const LOG_LINES: usize = 6;
+
+// Sends waiting to be written before a peer that isn't reading is hung up on
+const SEND_QUEUE_LEN: usize = 64;
+
+// How long one write may wait on a peer that isn't reading
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A TCP connection to the other end, read and written on background threads
+/// so a peer that stops reading can never hold up the game
+///
+#[derive(Debug)]
+pub struct Connection {
+    stream: TcpStream,
+    incoming: Receiver<Message>, // disconnected once the peer hangs up or sends garbage
+    outgoing: SyncSender<String>, // lines for the writer thread, gone once a write fails
+}
+
+impl Connection {
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nodelay(true)?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream.try_clone()?;
+        let (tx, incoming) = mpsc::channel();
+        let (outgoing, lines) = mpsc::sync_channel::<String>(SEND_QUEUE_LEN);
+
+        // Writes whatever's queued, even after the connection is dropped, then hangs up
+        thread::spawn(move || {
+            for lines in lines {
+                if writer.write_all(lines.as_bytes()).is_err() {
+                    break;
+                }
+            }
+            let _ = writer.shutdown(Shutdown::Both);
+        });
+
+        thread::spawn(move || {
+            for line in reader.lines() {
+                // Anything that isn't a message ends the connection
+                let Ok(message) = line.and_then(|line| {
+                    Message::from_line(&line)
+                        .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+                }) else {
+                    break;
+                };
+                if tx.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            stream,
+            incoming,
+            outgoing,
+        })
+    }
+
+    /// Queue messages to be sent in a single write, so they arrive together.
+    /// A peer too far behind to take them, or whose writes failed, is hung up on
+    /// and so drops out once its reader notices
+    ///
+    pub fn send(&mut self, messages: &[Message]) -> io::Result<()> {
+        let lines = messages.iter().map(Message::to_line).collect::<String>();
+        self.outgoing.try_send(lines).map_err(|_| {
+            let _ = self.stream.shutdown(Shutdown::Both);
+            io::Error::new(ErrorKind::BrokenPipe, "peer isn't keeping up")
+        })
+    }
+
+    /// Next message from the peer, if one has arrived
+    ///
+    pub fn try_recv(&self) -> Result<Message, TryRecvError> {
+        self.incoming.try_recv()
+    }
+}
+
+impl Drop for Connection {
+    // Wakes the reader thread so it can finish. The writer hangs up once it's sent what's queued
+    fn drop(&mut self) {
+        let _ = self.stream.shutdown(Shutdown::Read);
+    }
+}
+
+#[derive(Debug)]
+enum Role {
+    Host {
+        listener: TcpListener,
+        rules: Message,             // sent to whoever joins
+        history: Vec<Message>,      // every action applied, replayed to a peer that rejoins
+        shuffle: HostShuffle,       // for the current match
+        pending: Vec<Connection>,   // connected but yet to say hello
+        spectators: Vec<Spectator>, // everyone watching
+        // Both hands as they were after each change, held back from spectators until due
+        delayed_hands: VecDeque<(Instant, Message)>,
+        shown_hands: Option<Message>, // the latest hands spectators have been shown
+        seat_token: Option<String>,   // given to whoever took the seat, needed to take it back
+    },
+    Client {
+        addr: SocketAddr,
+        spectating: bool,           // only watching, nothing we send moves the game
+        deck: RelayedDeck,          // the game's deck, dealing the host's cards
+        expected_hash: Option<u64>, // our table's hash after the last action from the host
+        shuffle: Option<ClientShuffle>, // for the current match, once agreed
+        sent_seed: Option<(String, Seed)>, // the last commitment answered, and our answer
+        token: Option<String>,      // from the host, to get our seat back with
+    },
+}
+
+// The host's side of a match's shuffle
+#[derive(Debug)]
+struct HostShuffle {
+    secret: Seed,
+    client_seed: Option<Seed>, // nothing is dealt until the client has sent theirs
+    revealed: bool,
+}
+
+impl HostShuffle {
+    fn new() -> Self {
+        Self {
+            secret: Seed::random(),
+            client_seed: None,
+            revealed: false,
+        }
+    }
+}
+
+// Someone watching a hosted game
+#[derive(Debug)]
+struct Spectator {
+    connection: Connection,
+}
+
+// What a client knows of a match's shuffle, to check the deal once it's revealed
+#[derive(Debug)]
+struct ClientShuffle {
+    commitment: String,
+    client_seed: Seed,
+    ours: bool,      // whether we sent client_seed, which a client that rejoins can't know
+    dealt: Vec<i32>, // dealer cards shown this match, in order
+}
+
+#[derive(Debug)]
+enum Link {
+    Waiting, // host: nobody has taken the other seat yet
+    Joining {
+        connection: Connection,
+        lost_since: Option<Instant>, // when the previous connection dropped, for a rejoin
+    },
+    Connected(Connection),
+    Lost {
+        since: Instant,
+        retry: Option<Receiver<TcpStream>>, // client: connections made by the retry thread
+    },
+    Closed {
+        reason: String,
+    },
+}
+
+/// A game against someone over the network, from this end of the table.
+/// Whoever plays at this end always sits in Player's seat
+///
+#[derive(Debug)]
+pub struct NetSession {
+    pub game_state: GameState,
+    pub name: String,
+    pub peer_name: Option<String>,
+    pub chat: Vec<String>, // "name: text", oldest first
+    pub reconnect_window: Duration,
+    pub spectator_hands: Option<Duration>, // host: how far behind the game spectators see hands
+    role: Role,
+    link: Link,
+    shown_countdown: Option<u64>, // seconds left last time the reconnect countdown changed
+    show_log: bool,               // spectator: whether the action log is over the board
+}
+
+impl NetSession {
+    /// Host a game, waiting for someone to join on `listener`
+    ///
+    pub fn host(listener: TcpListener, name: &str) -> io::Result<Self> {
+        // Polled for new connections every update instead of blocking
+        listener.set_nonblocking(true)?;
+
+        // Replaced by the agreed shuffle before anything is dealt
+        let deck = Box::new(RandomDeck::new());
+        let mut game_state = GameState::networked(deck, Player::Player);
+        game_state.seats[Player::Player].name = name.to_string();
+
+        let rules = Message::Rules {
+            target_score: TARGET_SCORE,
+            rounds_to_win: ROUNDS_TO_WIN,
+            hand: game_state.seats[Player::Opponent]
+                .hand
+                .iter()
+                .flatten()
+                .copied()
+                .collect(),
+            opponent_hand: game_state.seats[Player::Player].hand.len(),
+        };
+
+        Ok(Self {
+            game_state,
+            name: name.to_string(),
+            peer_name: None,
+            chat: vec![],
+            reconnect_window: Duration::from_secs(RECONNECT_WINDOW_SECS),
+            spectator_hands: None,
+            role: Role::Host {
+                listener,
+                rules,
+                history: vec![],
+                shuffle: HostShuffle::new(),
+                pending: vec![],
+                spectators: vec![],
+                delayed_hands: VecDeque::new(),
+                shown_hands: None,
+                seat_token: None,
+            },
+            link: Link::Waiting,
+            shown_countdown: None,
+            show_log: false,
+        })
+    }
+
+    /// Join the game hosted at the other end of `stream`
+    ///
+    pub fn join(stream: TcpStream, name: &str) -> io::Result<Self> {
+        Self::rejoin(stream, name, None)
+    }
+
+    /// Join the game hosted at the other end of `stream`, taking back the seat `token`
+    /// was given for by an earlier connection
+    ///
+    pub fn rejoin(stream: TcpStream, name: &str, token: Option<String>) -> io::Result<Self> {
+        let deck = RelayedDeck::new();
+        // Only a placeholder until the host's rules arrive
+        let mut game_state = GameState::networked(Box::new(deck.clone()), Player::Opponent);
+        game_state.seats[Player::Player].name = name.to_string();
+
+        Self::client(stream, name, game_state, deck, false, token)
+    }
+
+    /// Watch the game hosted at the other end of `stream`, with seats the way the host sees them
+    ///
+    pub fn watch(stream: TcpStream, name: &str) -> io::Result<Self> {
+        let deck = RelayedDeck::new();
+        // Only a placeholder until the host's snapshot arrives
+        let game_state = GameState::spectated(Box::new(deck.clone()));
+
+        Self::client(stream, name, game_state, deck, true, None)
+    }
+
+    fn client(
+        stream: TcpStream,
+        name: &str,
+        game_state: GameState,
+        deck: RelayedDeck,
+        spectating: bool,
+        token: Option<String>,
+    ) -> io::Result<Self> {
+        let addr = stream.peer_addr()?;
+        let mut session = Self {
+            game_state,
+            name: name.to_string(),
+            peer_name: None,
+            chat: vec![],
+            reconnect_window: Duration::from_secs(RECONNECT_WINDOW_SECS),
+            spectator_hands: None,
+            role: Role::Client {
+                addr,
+                spectating,
+                deck,
+                expected_hash: None,
+                shuffle: None,
+                sent_seed: None,
+                token,
+            },
+            link: Link::Waiting,
+            shown_countdown: None,
+            show_log: spectating,
+        };
+        session.start_joining(Connection::new(stream)?, None);
+
+        Ok(session)
+    }
+
+    /// Port a host is listening on
+    ///
+    pub fn port(&self) -> Option<u16> {
+        match &self.role {
+            Role::Host { listener, .. } => listener.local_addr().ok().map(|addr| addr.port()),
+            Role::Client { .. } => None,
+        }
+    }
+
+    /// Token the host gave us for our seat, to take it back with on another connection
+    ///
+    pub fn rejoin_token(&self) -> Option<&str> {
+        match &self.role {
+            Role::Client { token, .. } => token.as_deref(),
+            Role::Host { .. } => None,
+        }
+    }
+
+    /// Is the game under way with both ends connected?
+    ///
+    pub fn is_connected(&self) -> bool {
+        matches!(self.link, Link::Connected(_))
+    }
+
+    /// Is this end only watching?
+    ///
+    pub fn is_spectating(&self) -> bool {
+        matches!(
+            self.role,
+            Role::Client {
+                spectating: true,
+                ..
+            }
+        )
+    }
+
+    /// Number of people watching a hosted game
+    ///
+    pub fn spectator_count(&self) -> usize {
+        match &self.role {
+            Role::Host { spectators, .. } => spectators.len(),
+            Role::Client { .. } => 0,
+        }
+    }
+
+    /// Have both ends agreed the current match's shuffle? Nothing is dealt until they have
+    ///
+    pub fn is_shuffled(&self) -> bool {
+        match &self.role {
+            Role::Host { shuffle, .. } => shuffle.client_seed.is_some(),
+            Role::Client { shuffle, .. } => shuffle.is_some(),
+        }
+    }
+
+    /// Why the game ended for good, if it has
+    ///
+    pub fn closed_reason(&self) -> Option<&str> {
+        match &self.link {
+            Link::Closed { reason } => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// A key pressed at this end: 'r' resigns, anything else is looked up as a game action.
+    /// A spectator can only show or hide the action log, with 'l'
+    ///
+    pub fn handle_game_input(&mut self, key: char) {
+        if !self.is_connected() {
+            return;
+        }
+        if self.is_spectating() {
+            if key == 'l' {
+                self.show_log = !self.show_log;
+            }
+            return;
+        }
+
+        if key == 'r' {
+            self.request(Message::Resign {
+                seat: self.host_seat(Player::Player),
+            });
+        } else if let Some(action) = self.game_state.game_action_from_key(key) {
+            self.request_action(action);
+        }
+    }
+
+    /// Make a move or table action from this end. The host applies it if it's allowed,
+    /// a client asks the host to
+    ///
+    pub fn request_action(&mut self, action: GameAction) {
+        if !self.is_connected() || self.is_spectating() {
+            return;
+        }
+        self.request(Message::Action {
+            seat: self.host_seat(Player::Player),
+            action,
+            card: None,
+        });
+    }
+
+    /// Send a chat message to the other end, and on to anyone watching
+    ///
+    pub fn send_chat(&mut self, text: &str) {
+        self.chat.push(format!("{}: {text}", self.name));
+        let chat = [Message::Chat {
+            from: self.name.clone(),
+            text: text.to_string(),
+        }];
+        self.tell_spectators(&chat);
+        self.send(&chat);
+    }
+
+    /// Check on the connection and handle anything the peer sent.
+    /// Returns whether anything changed
+    ///
+    pub fn update(&mut self) -> bool {
+        let mut changed = match self.role {
+            Role::Host { .. } => self.update_host(),
+            Role::Client { .. } => self.update_client(),
+        };
+        changed |= self.game_state.update();
+
+        // Redraw as the reconnect countdown ticks down
+        let countdown = self.countdown();
+        if countdown != self.shown_countdown {
+            self.shown_countdown = countdown;
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Messages can arrive at any time, so ask to be checked on regularly
+    ///
+    pub fn next_deadline(&self) -> Option<Instant> {
+        Some(Instant::now() + Duration::from_millis(NET_POLL_MS))
+    }
+
+    /// What to show over the board while the game can't go on, or None while it can.
+    /// A spectator sees the action log there instead, unless they've hidden it
+    ///
+    pub fn overlay_text(&self) -> Option<Vec<String>> {
+        let peer = self.peer_name.as_deref();
+        let is_host = matches!(self.role, Role::Host { .. });
+
+        let text = match &self.link {
+            Link::Connected(_) if self.show_log => self.action_log(),
+            Link::Connected(_) => return None,
+            Link::Waiting => vec![
+                format!(
+                    "Waiting for someone to join on port {}.",
+                    self.port().unwrap_or_default()
+                ),
+                "They can join with: kaazap --join <this machine's address>".to_string(),
+            ],
+            Link::Joining { .. } => vec![format!("Joining {}...", peer.unwrap_or("the host"))],
+            Link::Lost { .. } => {
+                let seconds = self.countdown().unwrap_or_default();
+                if is_host {
+                    vec![
+                        format!("{} lost their connection.", peer.unwrap_or("Your opponent")),
+                        format!("Waiting {seconds}s for them to come back."),
+                    ]
+                } else {
+                    vec![
+                        format!("Lost the connection to {}.", peer.unwrap_or("the host")),
+                        format!("Trying to reconnect for {seconds}s."),
+                    ]
+                }
+            }
+            Link::Closed { reason } => vec![
+                reason.clone(),
+                "Press 'x' to return to the menu.".to_string(),
+            ],
+        };
+
+        Some(text)
+    }
+
+    // The spectator's view: who's playing, then the last few actions
+    fn action_log(&self) -> Vec<String> {
+        let seats = &self.game_state.seats;
+        let mut lines = vec![format!(
+            "Watching {} against {}. 'l' hides this.",
+            seats[Player::Player].name,
+            seats[Player::Opponent].name
+        )];
+
+        let events = self.game_state.recent_events().collect::<Vec<_>>();
+        let skip = events.len().saturating_sub(LOG_LINES);
+        lines.extend(
+            events[skip..]
+                .iter()
+                .map(|event| self.game_state.describe(event)),
+        );
+
+        lines
+    }
+
+    // Seats in messages are numbered as the host sees the table. A client sits in the host's
+    // Opponent seat, so its seats are the other way round. Swapping works in both directions.
+    // A spectator sees the table the host's way round
+    fn host_seat(&self, seat: Player) -> Player {
+        match self.role {
+            Role::Host { .. }
+            | Role::Client {
+                spectating: true, ..
+            } => seat,
+            Role::Client { .. } => seat.other(),
+        }
+    }
+
+    // Our move or resignation: a host applies it, a client asks the host to
+    fn request(&mut self, message: Message) {
+        match (&self.role, message) {
+            (Role::Host { .. }, Message::Action { seat, action, .. }) => {
+                self.host_apply(seat, action);
+            }
+            (Role::Host { .. }, Message::Resign { seat }) => self.host_resign(seat),
+            (Role::Client { .. }, message) => self.send(&[message]),
+            _ => {}
+        }
+    }
+
+    // Send to the peer if connected. A failed send shows up as the reader hanging up
+    fn send(&mut self, messages: &[Message]) {
+        if let Link::Connected(connection) = &mut self.link {
+            let _ = connection.send(messages);
+        }
+    }
+
+    // Seconds left for the peer to reconnect, while waiting on them
+    fn countdown(&self) -> Option<u64> {
+        let Link::Lost { since, .. } = self.link else {
+            return None;
+        };
+        let left = self.reconnect_window.saturating_sub(since.elapsed());
+        Some(left.as_secs_f32().ceil() as u64)
+    }
+
+    fn window_expired(&self) -> bool {
+        self.countdown() == Some(0)
+    }
+
+    fn start_joining(&mut self, mut connection: Connection, lost_since: Option<Instant>) {
+        if let Role::Client { .. } = self.role {
+            let _ = connection.send(&[self.hello()]);
+        }
+        self.link = Link::Joining {
+            connection,
+            lost_since,
+        };
+    }
+
+    // A client sends its token, if it has one, to get its seat back
+    fn hello(&self) -> Message {
+        Message::Hello {
+            version: PROTOCOL_VERSION,
+            name: self.name.clone(),
+            spectator: self.is_spectating(),
+            token: self.rejoin_token().map(str::to_string),
+        }
+    }
+
+    // Next message from the peer. Ok(None) if there's nothing yet, Err if they've gone
+    fn receive(&self) -> Result<Option<Message>, ()> {
+        let connection = match &self.link {
+            Link::Joining { connection, .. } | Link::Connected(connection) => connection,
+            _ => return Ok(None),
+        };
+
+        match connection.try_recv() {
+            Ok(message) => Ok(Some(message)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(()),
+        }
+    }
+
+    // --- Host ---
+
+    fn update_host(&mut self) -> bool {
+        let mut changed = self.accept_connections();
+        changed |= self.greet_newcomers();
+        changed |= self.update_spectators();
+
+        loop {
+            match self.receive() {
+                Ok(Some(message)) => {
+                    self.host_receive(message);
+                    changed = true;
+                }
+                Ok(None) => break,
+                Err(()) => {
+                    self.peer_dropped();
+                    changed = true;
+                    break;
+                }
+            }
+        }
+
+        // The peer didn't come back in time, so they forfeit
+        if self.window_expired() {
+            self.host_resign(Player::Opponent);
+            self.link = Link::Closed {
+                reason: format!(
+                    "{} didn't come back, so you win.",
+                    self.peer_name.as_deref().unwrap_or("Your opponent")
+                ),
+            };
+            changed = true;
+        }
+
+        changed
+    }
+
+    // Hold on to new connections until they say whether they're playing or watching
+    fn accept_connections(&mut self) -> bool {
+        let Role::Host {
+            listener, pending, ..
+        } = &mut self.role
+        else {
+            return false;
+        };
+
+        let mut accepted = false;
+        while let Ok((stream, _)) = listener.accept() {
+            if stream.set_nonblocking(false).is_ok()
+                && let Ok(connection) = Connection::new(stream)
+            {
+                pending.push(connection);
+                accepted = true;
+            }
+        }
+
+        accepted
+    }
+
+    // Answer everyone who's said hello, forgetting anyone who left or said anything else first
+    fn greet_newcomers(&mut self) -> bool {
+        let Role::Host { pending, .. } = &mut self.role else {
+            return false;
+        };
+
+        let mut changed = false;
+        let mut still_pending = vec![];
+        for connection in std::mem::take(pending) {
+            match connection.try_recv() {
+                Err(TryRecvError::Empty) => still_pending.push(connection),
+                Ok(Message::Hello {
+                    version,
+                    name,
+                    spectator,
+                    token,
+                }) => {
+                    self.welcome(connection, version, name, spectator, token);
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+
+        if let Role::Host { pending, .. } = &mut self.role {
+            pending.append(&mut still_pending);
+        }
+        changed
+    }
+
+    // Forget spectators who've left, and show everyone both hands as they were
+    // spectator_hands ago
+    fn update_spectators(&mut self) -> bool {
+        let Role::Host {
+            spectators,
+            delayed_hands,
+            shown_hands,
+            ..
+        } = &mut self.role
+        else {
+            return false;
+        };
+
+        let mut hands = None;
+        while let Some((taken, _)) = delayed_hands.front()
+            && self
+                .spectator_hands
+                .is_some_and(|delay| taken.elapsed() >= delay)
+        {
+            hands = delayed_hands.pop_front().map(|(_, message)| message);
+        }
+        if let Some(hands) = &hands {
+            *shown_hands = Some(hands.clone());
+        }
+
+        let watching = spectators.len();
+        spectators.retain_mut(|spectator| {
+            // Spectators have nothing to say, all that matters is whether they've gone
+            loop {
+                match spectator.connection.try_recv() {
+                    Ok(_) => {}
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => return false,
+                }
+            }
+
+            if let Some(hands) = &hands {
+                let _ = spectator.connection.send(std::slice::from_ref(hands));
+            }
+            true
+        });
+
+        spectators.len() != watching
+    }
+
+    fn host_receive(&mut self, message: Message) {
+        match message {
+            // A client only moves its own seat
+            Message::Action { seat, action, .. } if seat == Player::Opponent => {
+                self.host_apply(seat, action);
+            }
+            Message::Resign { seat } if seat == Player::Opponent => self.host_resign(seat),
+            // Said by whoever's in the other seat, whatever name it claims
+            Message::Chat { text, .. } => {
+                let from = self.game_state.seats[Player::Opponent].name.clone();
+                let text = chat::clean(&text);
+                self.chat.push(format!("{from}: {text}"));
+                self.tell_spectators(&[Message::Chat { from, text }]);
+            }
+            Message::Seed { seed } => self.agree_shuffle(seed),
+            _ => {}
+        }
+    }
+
+    // Answer a newcomer's hello, then either hang up or bring them up to date
+    fn welcome(
+        &mut self,
+        mut connection: Connection,
+        version: u32,
+        name: String,
+        spectator: bool,
+        token: Option<String>,
+    ) {
+        let hello = self.hello();
+        if version != PROTOCOL_VERSION {
+            let _ = connection.send(&[hello]);
+            return;
+        }
+        let name = chat::clean(&name);
+
+        if spectator {
+            if let Role::Host {
+                spectators,
+                shown_hands,
+                ..
+            } = &mut self.role
+            {
+                let mut messages = vec![hello, Message::snapshot(&self.game_state)];
+                messages.extend(shown_hands.clone());
+                let _ = connection.send(&messages);
+                spectators.push(Spectator { connection });
+            }
+            self.chat.push(format!("* {name} is watching"));
+            return;
+        }
+
+        let closed = matches!(self.link, Link::Closed { .. });
+        let Role::Host {
+            rules,
+            history,
+            shuffle,
+            seat_token,
+            ..
+        } = &mut self.role
+        else {
+            return;
+        };
+
+        // The seat's free until someone takes it, then only the token they were given takes
+        // it back, whatever name is used. They may be back before their old connection is
+        // noticed to have dropped, which it replaces
+        let may_sit = match seat_token {
+            None => true,
+            Some(seat_token) => token.as_ref() == Some(seat_token) && !closed,
+        };
+        if !may_sit {
+            let _ = connection.send(&[hello]);
+            return;
+        }
+        let token = seat_token.get_or_insert_with(|| format!("{:032x}", rand::random::<u128>()));
+        let hello = Message::Hello {
+            version: PROTOCOL_VERSION,
+            name: self.name.clone(),
+            spectator: false,
+            token: Some(token.clone()),
+        };
+
+        // Everything so far, so a rejoining peer ends up where they left off
+        let mut messages = vec![hello, rules.clone()];
+        messages.extend(history.iter().cloned());
+        messages.push(Message::StateHash {
+            hash: table_hash(&self.game_state, Player::Player),
+        });
+        // Ask for their part of the shuffle if this match's is still to be agreed
+        if shuffle.client_seed.is_none() {
+            messages.push(Message::Commit {
+                commitment: shuffle.secret.commitment(),
+                seed: None,
+            });
+        }
+        let _ = connection.send(&messages);
+
+        self.game_state.seats[Player::Opponent].name = name.clone();
+        self.peer_name = Some(name);
+        self.link = Link::Connected(connection);
+    }
+
+    // Apply a move or table action for `seat`, then tell the client about it
+    fn host_apply(&mut self, seat: Player, action: GameAction) {
+        let applied = match action {
+            // Nobody moves until the shuffle's agreed
+            GameAction::Hit
+            | GameAction::Stand
+            | GameAction::PlayHand { .. }
+            | GameAction::PlayFlipped { .. } => {
+                self.is_shuffled() && self.game_state.apply_seat_action(seat, action)
+            }
+            GameAction::NextRound | GameAction::NextGame => {
+                self.game_state.apply_game_action(action)
+            }
+            GameAction::TakeTurn => false,
+        };
+        if !applied {
+            return;
+        }
+
+        // The card the client can't know: the one dealt, or the side card played
+        let side = &self.game_state.seats[seat];
+        let card = match action {
+            GameAction::Hit => side.dealer_row.last(),
+            GameAction::PlayHand { .. } | GameAction::PlayFlipped { .. } => side.played_row.last(),
+            _ => None,
+        };
+        let card = card.map(|card| card.value);
+
+        self.broadcast(Message::Action { seat, action, card });
+
+        if action == GameAction::NextGame {
+            self.new_shuffle();
+        }
+    }
+
+    fn host_resign(&mut self, seat: Player) {
+        if self.game_state.resign(seat) {
+            self.broadcast(Message::Resign { seat });
+        }
+    }
+
+    // Record an action that's been applied and send it on with the new table hash.
+    // The match's seeds go out too once it's over
+    fn broadcast(&mut self, message: Message) {
+        settle(&mut self.game_state);
+        let hash = Message::StateHash {
+            hash: table_hash(&self.game_state, Player::Player),
+        };
+
+        self.tell_spectators(&[message.clone(), hash.clone()]);
+        self.record(message);
+        self.send(&[hash]);
+        self.hold_back_hands();
+
+        if let GamePhase::GameOver { .. } = self.game_state.game_phase
+            && let Role::Host { shuffle, .. } = &mut self.role
+            && let Some(client_seed) = shuffle.client_seed
+            && !shuffle.revealed
+        {
+            shuffle.revealed = true;
+            let reveal = Message::Reveal {
+                host_seed: shuffle.secret,
+                client_seed,
+            };
+            self.record(reveal);
+        }
+    }
+
+    // Send a message to the client and keep it to replay to them if they rejoin
+    fn record(&mut self, message: Message) {
+        if let Role::Host { history, .. } = &mut self.role {
+            history.push(message.clone());
+        }
+        self.send(&[message]);
+    }
+
+    // Send to everyone watching. Anyone who's gone is dropped on the next update
+    fn tell_spectators(&mut self, messages: &[Message]) {
+        if let Role::Host { spectators, .. } = &mut self.role {
+            for spectator in spectators {
+                let _ = spectator.connection.send(messages);
+            }
+        }
+    }
+
+    // Commit to a fresh seed for the next match and ask the client for theirs
+    fn new_shuffle(&mut self) {
+        let Role::Host { shuffle, .. } = &mut self.role else {
+            return;
+        };
+        *shuffle = HostShuffle::new();
+
+        let commitment = shuffle.secret.commitment();
+        self.send(&[Message::Commit {
+            commitment,
+            seed: None,
+        }]);
+    }
+
+    // The client's part of the shuffle arrived: deal from both seeds and confirm which was used
+    fn agree_shuffle(&mut self, seed: Seed) {
+        let Role::Host { shuffle, .. } = &mut self.role else {
+            return;
+        };
+        // Only the first answer counts, the deal can't change once it's started
+        if shuffle.client_seed.is_some() {
+            return;
+        }
+        shuffle.client_seed = Some(seed);
+
+        let commitment = shuffle.secret.commitment();
+        let deck = shuffle::deck(&shuffle.secret, &seed);
+        self.game_state.set_deck(Box::new(deck));
+        self.record(Message::Commit {
+            commitment,
+            seed: Some(seed),
+        });
+        self.hold_back_hands();
+    }
+
+    // Take both hands as they are now, to show spectators once spectator_hands has passed
+    fn hold_back_hands(&mut self) {
+        if let Role::Host { delayed_hands, .. } = &mut self.role
+            && self.spectator_hands.is_some()
+        {
+            delayed_hands.push_back((Instant::now(), Message::hands(&self.game_state)));
+        }
+    }
+
+    // --- Client ---
+
+    fn update_client(&mut self) -> bool {
+        let mut changed = false;
+
+        loop {
+            match self.receive() {
+                Ok(Some(message)) => {
+                    self.client_receive(message);
+                    changed = true;
+                }
+                Ok(None) => break,
+                Err(()) => {
+                    self.peer_dropped();
+                    changed = true;
+                    break;
+                }
+            }
+        }
+
+        if let Link::Lost {
+            since,
+            retry: Some(retry),
+        } = &self.link
+        {
+            if let Ok(stream) = retry.try_recv() {
+                let since = *since;
+                match Connection::new(stream) {
+                    Ok(connection) => self.start_joining(connection, Some(since)),
+                    Err(_) => self.peer_dropped(),
+                }
+                changed = true;
+            } else if self.window_expired() {
+                self.link = Link::Closed {
+                    reason: format!(
+                        "Couldn't get back to {}.",
+                        self.peer_name.as_deref().unwrap_or("the host")
+                    ),
+                };
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    fn client_receive(&mut self, message: Message) {
+        match message {
+            Message::Hello {
+                version,
+                name,
+                token: host_token,
+                ..
+            } => {
+                if version != PROTOCOL_VERSION {
+                    self.close(format!(
+                        "The host plays protocol version {version}, this is version \
+                         {PROTOCOL_VERSION}."
+                    ));
+                    return;
+                }
+                self.peer_name = Some(chat::clean(&name));
+                if let Role::Client { token, .. } = &mut self.role
+                    && host_token.is_some()
+                {
+                    *token = host_token;
+                }
+            }
+            Message::Rules {
+                target_score,
+                rounds_to_win,
+                hand,
+                opponent_hand,
+            } => {
+                if target_score != TARGET_SCORE || rounds_to_win != ROUNDS_TO_WIN {
+                    self.close("The host plays by different rules.".to_string());
+                    return;
+                }
+                self.start_mirror(&hand, opponent_hand);
+            }
+            Message::Snapshot {
+                seats,
+                phase,
+                round_outcome,
+            } => self.start_watching(seats, phase, round_outcome),
+            Message::Hands { player, opponent } if self.is_spectating() => {
+                // The hands as they were a while ago, so only slots still filled take their
+                // values from it. Cards played since are already in the played rows
+                for (seat, shown) in [(Player::Player, player), (Player::Opponent, opponent)] {
+                    for (slot, shown) in self.game_state.seats[seat].hand.iter_mut().zip(shown) {
+                        if let (Some(card), Some(shown)) = (slot, shown) {
+                            *card = shown;
+                        }
+                    }
+                }
+                self.game_state.open_hands = true;
+            }
+            Message::Action { seat, action, card } => {
+                if !self.mirror_action(self.host_seat(seat), action, card) {
+                    self.close("Out of step with the host.".to_string());
+                }
+            }
+            Message::StateHash { hash } => {
+                if let Role::Client { expected_hash, .. } = &self.role
+                    && *expected_hash != Some(hash)
+                {
+                    self.close("Out of step with the host.".to_string());
+                }
+            }
+            Message::Chat { from, text } => {
+                self.chat
+                    .push(format!("{}: {}", chat::clean(&from), chat::clean(&text)))
+            }
+            Message::Resign { seat } => {
+                self.game_state.resign(self.host_seat(seat));
+                self.record_hash();
+            }
+            Message::Commit { commitment, seed } => self.client_commit(commitment, seed),
+            Message::Reveal {
+                host_seed,
+                client_seed,
+            } => self.check_deal(host_seed, client_seed),
+            Message::Seed { .. } | Message::Hands { .. } => {}
+        }
+    }
+
+    // Answer a commitment with our part of the shuffle, or note the shuffle the host confirms.
+    // Once we've answered, the host must confirm the very commitment and seed we answered:
+    // anything else would let it pick its secret after seeing our seed
+    fn client_commit(&mut self, commitment: String, seed: Option<Seed>) {
+        let Role::Client {
+            shuffle, sent_seed, ..
+        } = &mut self.role
+        else {
+            return;
+        };
+
+        let Some(seed) = seed else {
+            let seed = Seed::random();
+            *sent_seed = Some((commitment, seed));
+            self.send(&[Message::Seed { seed }]);
+            return;
+        };
+
+        let (commitment, ours) = match sent_seed.take() {
+            Some((sent, answer)) if sent == commitment && answer == seed => (sent, true),
+            Some(_) => {
+                self.close("The host changed the shuffle after seeing our part of it.".to_string());
+                return;
+            }
+            None => (commitment, false),
+        };
+        *shuffle = Some(ClientShuffle {
+            commitment,
+            client_seed: seed,
+            ours,
+            dealt: vec![],
+        });
+    }
+
+    // The match is over: check every dealer card shown came from the agreed shuffle
+    fn check_deal(&mut self, host_seed: Seed, client_seed: Seed) {
+        let Role::Client { shuffle, .. } = &mut self.role else {
+            return;
+        };
+        let Some(shuffle) = shuffle.take() else {
+            return;
+        };
+
+        let checked = if client_seed == shuffle.client_seed {
+            shuffle::verify_deal(
+                &shuffle.commitment,
+                &host_seed,
+                &client_seed,
+                &shuffle.dealt,
+            )
+        } else {
+            Err("the host revealed a different seed of ours".to_string())
+        };
+
+        match checked {
+            Err(err) => self.close(format!("The host's deal can't be trusted: {err}.")),
+            Ok(()) if shuffle.ours => self.chat.push(format!(
+                "* Fair deal: all {} dealer cards came from the agreed shuffle",
+                shuffle.dealt.len()
+            )),
+            Ok(()) => self.chat.push(format!(
+                "* All {} dealer cards came from the shuffle, but you rejoined after it was \
+                 agreed so your part of it couldn't be checked",
+                shuffle.dealt.len()
+            )),
+        }
+    }
+
+    // Start again from the host's rules, ready for the actions that follow
+    fn start_mirror(&mut self, hand: &[LogicCard], opponent_hand: usize) {
+        let Role::Client {
+            deck,
+            shuffle,
+            sent_seed,
+            ..
+        } = &mut self.role
+        else {
+            return;
+        };
+        *deck = RelayedDeck::new();
+        *shuffle = None;
+        // The host replays every shuffle it confirmed, and an answer sent over an earlier
+        // connection can't be held to, so shuffles agreed before a rejoin aren't ours
+        *sent_seed = None;
+
+        let mut game_state = GameState::networked(Box::new(deck.clone()), Player::Opponent);
+        game_state.seats[Player::Player].name = self.name.clone();
+        game_state.seats[Player::Player].hand = hand.iter().copied().map(Some).collect();
+        game_state.seats[Player::Opponent].name = self.peer_name.clone().unwrap_or_default();
+        // Blanks stand in for the host's side cards until they're played
+        game_state.seats[Player::Opponent].hand = vec![Some(LogicCard::new(0)); opponent_hand];
+        self.game_state = game_state;
+        self.record_hash();
+        self.joined();
+    }
+
+    // Set the table up from the host's snapshot, ready for the actions that follow
+    fn start_watching(
+        &mut self,
+        seats: Vec<SeatSnapshot>,
+        phase: TablePhase,
+        round_outcome: Option<RoundOutcome>,
+    ) {
+        let Role::Client { deck, .. } = &mut self.role else {
+            return;
+        };
+        *deck = RelayedDeck::new();
+
+        let mut game_state = GameState::spectated(Box::new(deck.clone()));
+        for (seat, snapshot) in Player::ALL.into_iter().zip(seats) {
+            let side = &mut game_state.seats[seat];
+            side.name = chat::clean(&snapshot.name);
+            side.dealer_row = cards(&snapshot.dealer_row);
+            side.played_row = cards(&snapshot.played_row);
+            // Blanks stand in for side cards until they're played or the host shows them
+            side.hand = snapshot
+                .hand
+                .iter()
+                .map(|&filled| filled.then_some(LogicCard::new(0)))
+                .collect();
+            side.stood = snapshot.stood;
+            side.bust = snapshot.bust;
+            side.rounds_won = snapshot.rounds_won;
+        }
+        game_state.game_phase = phase.into();
+        game_state.round_outcome = round_outcome;
+
+        self.game_state = game_state;
+        self.record_hash();
+        self.joined();
+    }
+
+    // The host has brought us up to date, so the game's under way
+    fn joined(&mut self) {
+        if let Link::Joining { .. } = self.link
+            && let Link::Joining { connection, .. } =
+                std::mem::replace(&mut self.link, Link::Waiting)
+        {
+            self.link = Link::Connected(connection);
+        }
+    }
+
+    // Apply an action the host has confirmed, returning false if we can't
+    fn mirror_action(&mut self, seat: Player, action: GameAction, card: Option<i32>) -> bool {
+        let Role::Client {
+            deck,
+            shuffle,
+            spectating,
+            ..
+        } = &mut self.role
+        else {
+            return false;
+        };
+
+        if let Some(value) = card {
+            match action {
+                GameAction::Hit => {
+                    // A player can't check a card dealt before the shuffle was agreed.
+                    // Spectators aren't told about shuffles, so they take the host's word
+                    match shuffle {
+                        Some(shuffle) => shuffle.dealt.push(value),
+                        None if *spectating => {}
+                        None => return false,
+                    }
+                    deck.push(LogicCard::new(value));
+                }
+                GameAction::PlayHand { index } => {
+                    if let Some(slot @ Some(_)) = self.game_state.seats[seat].hand.get_mut(index) {
+                        *slot = Some(LogicCard::new(value));
+                    }
+                }
+                // Face up the way it was played, so the flip turns it back
+                GameAction::PlayFlipped { index } => {
+                    if let Some(slot @ Some(_)) = self.game_state.seats[seat].hand.get_mut(index) {
+                        *slot = Some(LogicCard::plus_minus(-value));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let applied = match action {
+            GameAction::Hit
+            | GameAction::Stand
+            | GameAction::PlayHand { .. }
+            | GameAction::PlayFlipped { .. } => self.game_state.apply_seat_action(seat, action),
+            GameAction::NextRound | GameAction::NextGame => {
+                self.game_state.apply_game_action(action)
+            }
+            GameAction::TakeTurn => false,
+        };
+
+        settle(&mut self.game_state);
+        self.record_hash();
+        applied
+    }
+
+    fn record_hash(&mut self) {
+        let hash = table_hash(&self.game_state, self.host_seat(Player::Player));
+        if let Role::Client { expected_hash, .. } = &mut self.role {
+            *expected_hash = Some(hash);
+        }
+    }
+
+    fn close(&mut self, reason: String) {
+        self.link = Link::Closed { reason };
+    }
+
+    // --- Both ---
+
+    // The connection went: wait for the peer, unless there's no game left to come back to
+    fn peer_dropped(&mut self) {
+        let peer = self.peer_name.clone();
+        let host = peer.as_deref().unwrap_or("the host");
+        let game_over = matches!(self.game_state.game_phase, GamePhase::GameOver { .. });
+
+        self.link = match (&self.role, &self.link) {
+            // Spectators aren't waited for, so there's nothing to rejoin
+            (
+                Role::Client {
+                    spectating: true, ..
+                },
+                _,
+            ) => Link::Closed {
+                reason: format!("Lost the connection to {host}."),
+            },
+            // The host hung up before letting us in
+            (
+                Role::Client { .. },
+                Link::Joining {
+                    lost_since: None, ..
+                },
+            ) => Link::Closed {
+                reason: format!("{host} turned you away, someone else may be playing already."),
+            },
+            (_, _) if game_over => Link::Closed {
+                reason: format!(
+                    "{} left the game.",
+                    peer.as_deref().unwrap_or("Your opponent")
+                ),
+            },
+            (Role::Host { .. }, _) => Link::Lost {
+                since: Instant::now(),
+                retry: None,
+            },
+            (Role::Client { addr, .. }, link) => {
+                let since = match link {
+                    Link::Joining {
+                        lost_since: Some(since),
+                        ..
+                    } => *since,
+                    _ => Instant::now(),
+                };
+                Link::Lost {
+                    since,
+                    retry: Some(retry_connect(*addr, since + self.reconnect_window)),
+                }
+            }
+        };
+    }
+}
+
+// Run the game on until it's waiting on someone, so both ends see the same table after an action
+fn settle(game_state: &mut GameState) {
+    while game_state.update() {}
+}
+
+fn cards(values: &[i32]) -> Vec<LogicCard> {
+    values.iter().map(|&value| LogicCard::new(value)).collect()
+}
+
+// Keep trying to connect to `addr` on another thread until `deadline`.
+// The connection is sent back once made
+fn retry_connect(addr: SocketAddr, deadline: Instant) -> Receiver<TcpStream> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        while Instant::now() < deadline {
+            if let Ok(stream) = TcpStream::connect_timeout(&addr, RETRY_INTERVAL) {
+                let _ = tx.send(stream);
+                return;
+            }
+            thread::sleep(RETRY_INTERVAL);
+        }
+    });
+
+    rx
+}
@@ -0,0 +1,378 @@
+// Text-mode play, for screen readers, dumb terminals and scripted end-to-end tests.
+//
+// The same engine as the full screen board, but the table is printed as lines of text
+// after every move and moves are typed as commands, one per line.
+// A networked game is played the same way, with the table printed as it changes at either end.
+
+use std::{
+    io::{self, BufRead, Write},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    NET_POLL_MS,
+    game::{GameAction, GamePhase, GameState},
+    net::NetSession,
+    player::{Player, PlayerState},
+};
+
+const HELP: &str = "Commands: hit, stand, play N to play side card N, \
+                    play N + or play N - to play a ± card counting that way \
+                    or any other only if it already does, \
+                    next to start the next round or match, help, quit";
+
+// Nothing can be dealt, so nobody moves, until both ends have added to the shuffle
+const SHUFFLING: &str = "Agreeing the shuffle with the other side";
+
+/// Something typed at the prompt
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Hit,
+    Stand,
+    Play { index: usize, sign: Option<Sign> },
+    Next,
+    Help,
+    Quit,
+}
+
+/// Which way a side card is meant to count
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+    Plus,
+    Minus,
+}
+
+/// Read a command from a line of input, e.g. "play 3 -". Slots count from 1.
+/// Returns what's wrong with the line if it isn't a command
+///
+pub fn parse_command(line: &str) -> Result<Command, String> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+
+    match words.as_slice() {
+        ["hit"] => Ok(Command::Hit),
+        ["stand"] => Ok(Command::Stand),
+        ["next"] => Ok(Command::Next),
+        ["help"] => Ok(Command::Help),
+        ["quit"] => Ok(Command::Quit),
+        ["play", slot, sign @ ..] => {
+            let index = slot
+                .parse::<usize>()
+                .ok()
+                .and_then(|slot| slot.checked_sub(1))
+                .ok_or_else(|| format!("{slot} isn't a side card, they're numbered from 1"))?;
+            let sign = match sign {
+                [] => None,
+                ["+"] => Some(Sign::Plus),
+                ["-"] => Some(Sign::Minus),
+                _ => return Err("A side card is played as + or -".to_string()),
+            };
+            Ok(Command::Play { index, sign })
+        }
+        [] => Err("Type a command, or help to list them".to_string()),
+        _ => Err(format!("Unknown command {line}, type help to list them")),
+    }
+}
+
+/// Play `game_state` from `input`, printing the table to `output` after every move.
+/// Ends at quit or the end of the input
+///
+pub fn run(
+    mut game_state: GameState,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    writeln!(output, "{HELP}")?;
+    let mut lines = input.lines();
+
+    loop {
+        play_out(&mut game_state);
+        for event in game_state.take_events() {
+            writeln!(output, "{}", game_state.describe(&event))?;
+        }
+        for line in table_lines(&game_state) {
+            writeln!(output, "{line}")?;
+        }
+
+        // Ask again until something happens
+        loop {
+            let Some(line) = lines.next().transpose()? else {
+                return Ok(());
+            };
+            let command = match parse_command(&line) {
+                Ok(command) => command,
+                Err(reason) => {
+                    writeln!(output, "{reason}")?;
+                    continue;
+                }
+            };
+
+            match command {
+                Command::Quit => return Ok(()),
+                Command::Help => writeln!(output, "{HELP}")?,
+                _ => match apply_command(&mut game_state, command) {
+                    Ok(()) => break,
+                    Err(reason) => writeln!(output, "{reason}")?,
+                },
+            }
+        }
+    }
+}
+
+/// Play the networked game `session` from `input`, printing to `output` whenever the table
+/// changes at either end. Ends at quit, the end of the input or once the game is over for good
+///
+pub fn run_online(
+    mut session: NetSession,
+    input: impl BufRead + Send + 'static,
+    mut output: impl Write,
+) -> io::Result<()> {
+    writeln!(output, "{HELP}")?;
+    // Read on another thread, so the connection is looked after while waiting for a line
+    let lines = read_lines(input);
+    let mut shown = vec![];
+
+    loop {
+        session.update();
+        for event in session.game_state.take_events() {
+            writeln!(output, "{}", session.game_state.describe(&event))?;
+        }
+        if let Some(reason) = session.closed_reason() {
+            writeln!(output, "{reason}")?;
+            return Ok(());
+        }
+
+        // Waiting, reconnecting and the like are shown instead of the table
+        let table = session.overlay_text().unwrap_or_else(|| {
+            let mut lines = table_lines(&session.game_state);
+            if !session.is_shuffled() {
+                lines.pop();
+                lines.push(SHUFFLING.to_string());
+            }
+            lines
+        });
+        if table != shown {
+            for line in &table {
+                writeln!(output, "{line}")?;
+            }
+            shown = table;
+        }
+        output.flush()?;
+
+        let line = match lines.recv_timeout(Duration::from_millis(NET_POLL_MS)) {
+            Ok(line) => line,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        };
+        match parse_command(&line) {
+            Ok(Command::Quit) => return Ok(()),
+            Ok(Command::Help) => writeln!(output, "{HELP}")?,
+            Ok(command) => {
+                if let Err(reason) = request_command(&mut session, command) {
+                    writeln!(output, "{reason}")?;
+                }
+            }
+            Err(reason) => writeln!(output, "{reason}")?,
+        }
+    }
+}
+
+/// The table in words: each seat, the rounds won so far and what can be done next
+///
+pub fn table_lines(state: &GameState) -> Vec<String> {
+    let mut lines = Player::ALL
+        .into_iter()
+        .map(|seat| seat_line(&state.seats[seat], state.shows_hand(seat)))
+        .collect::<Vec<_>>();
+
+    let won = |seat: Player| {
+        let side = &state.seats[seat];
+        format!("{} {}", side.name, side.rounds_won)
+    };
+    lines.push(format!(
+        "Rounds won: {}, {}",
+        won(Player::Player),
+        won(Player::Opponent)
+    ));
+
+    lines.push(match state.game_phase {
+        GamePhase::Turn { seat } if state.active_seat() == Some(seat) => {
+            format!("{} to move: hit, stand or play N", state.seats[seat].name)
+        }
+        GamePhase::AwaitingNextRound => "Round over, type next to play on".to_string(),
+        GamePhase::GameOver { .. } => "Match over, type next for a new match".to_string(),
+        _ => "Waiting for the other side".to_string(),
+    });
+
+    lines
+}
+
+// e.g. "You: 14 from 7 4 and played +3, side cards 1) +5 2) used 3) +6 4) +2, standing"
+fn seat_line(side: &PlayerState, shows_hand: bool) -> String {
+    let dealt = if side.dealer_row.is_empty() {
+        "nothing dealt".to_string()
+    } else {
+        let cards = side
+            .dealer_row
+            .iter()
+            .map(|card| card.value.to_string())
+            .collect::<Vec<_>>();
+        format!("from {}", cards.join(" "))
+    };
+    let mut line = format!("{}: {} {dealt}", side.name, side.score());
+
+    if !side.played_row.is_empty() {
+        let cards = side
+            .played_row
+            .iter()
+            .map(|card| format!("{:+}", card.value))
+            .collect::<Vec<_>>();
+        line += &format!(" and played {}", cards.join(" "));
+    }
+
+    if shows_hand {
+        let slots = side
+            .hand
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| match slot {
+                Some(card) => format!("{}) {}", i + 1, card.label()),
+                None => format!("{}) used", i + 1),
+            })
+            .collect::<Vec<_>>();
+        line += &format!(", side cards {}", slots.join(" "));
+    } else {
+        let held = side.hand.iter().filter(|slot| slot.is_some()).count();
+        line += &format!(", {held} side cards");
+    }
+
+    if side.bust {
+        line += ", bust";
+    } else if side.stood {
+        line += ", standing";
+    }
+
+    line
+}
+
+// Make the move, or say why it can't be made
+fn apply_command(state: &mut GameState, command: Command) -> Result<(), String> {
+    let action = match command {
+        Command::Hit => GameAction::Hit,
+        Command::Stand => GameAction::Stand,
+        Command::Play { index, sign } => match state.active_seat() {
+            Some(seat) => side_card_action(&state.seats[seat], index, sign)?,
+            None => GameAction::PlayHand { index },
+        },
+        Command::Next => match state.game_phase {
+            GamePhase::GameOver { .. } => GameAction::NextGame,
+            _ => GameAction::NextRound,
+        },
+        Command::Help | Command::Quit => return Ok(()),
+    };
+
+    if state.apply_game_action(action) {
+        Ok(())
+    } else if state.active_seat().is_some() {
+        Err("That move isn't allowed now".to_string())
+    } else {
+        Err("It's not your move, type next to play on".to_string())
+    }
+}
+
+// Send the move to the host, or say why it can't be made. The host has the final say
+fn request_command(session: &mut NetSession, command: Command) -> Result<(), String> {
+    if !session.is_connected() {
+        return Err("Not connected, wait for the other side".to_string());
+    }
+    if !session.is_shuffled() {
+        return Err(SHUFFLING.to_string());
+    }
+
+    let state = &session.game_state;
+    let our_turn = state.active_seat() == Some(Player::Player);
+    let action = match command {
+        Command::Hit | Command::Stand | Command::Play { .. } if !our_turn => {
+            return Err("It's not your move".to_string());
+        }
+        Command::Hit => GameAction::Hit,
+        Command::Stand => GameAction::Stand,
+        Command::Play { index, sign } => {
+            side_card_action(&state.seats[Player::Player], index, sign)?
+        }
+        Command::Next => match state.game_phase {
+            GamePhase::GameOver { .. } => GameAction::NextGame,
+            _ => GameAction::NextRound,
+        },
+        Command::Help | Command::Quit => return Ok(()),
+    };
+
+    session.request_action(action);
+    Ok(())
+}
+
+// The move for playing side card `index`. A sign flips a ± card to count that way,
+// other side cards can't be flipped so it only checks they count the way that's expected
+fn side_card_action(
+    side: &PlayerState,
+    index: usize,
+    sign: Option<Sign>,
+) -> Result<GameAction, String> {
+    let slot = index + 1;
+    let Some(card) = side.hand.get(index) else {
+        return Err(format!("There's no side card {slot}"));
+    };
+    let Some(card) = card else {
+        return Err(format!("Side card {slot} has been played already"));
+    };
+
+    let counts = if card.value < 0 {
+        Sign::Minus
+    } else {
+        Sign::Plus
+    };
+    match sign {
+        Some(sign) if sign != counts && card.flippable => Ok(GameAction::PlayFlipped { index }),
+        Some(sign) if sign != counts => Err(format!(
+            "Side card {slot} is {:+} and can't be flipped",
+            card.value
+        )),
+        _ => Ok(GameAction::PlayHand { index }),
+    }
+}
+
+// Let the computer or a bot move until it's someone's turn at the keyboard or the round is over.
+// There's nothing to watch while the computer thinks, so it doesn't
+fn play_out(state: &mut GameState) {
+    loop {
+        state.skip_thinking();
+        if state.update() {
+            continue;
+        }
+        match state.next_deadline() {
+            Some(deadline) => thread::sleep(deadline.saturating_duration_since(Instant::now())),
+            None => return,
+        }
+    }
+}
+
+// Lines of `input`, sent on as they're read. Disconnected at the end of the input
+fn read_lines(input: impl BufRead + Send + 'static) -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for line in input.lines() {
+            let Ok(line) = line else {
+                return;
+            };
+            if tx.send(line).is_err() {
+                return;
+            }
+        }
+    });
+
+    rx
+}
@@ -1,13 +1,69 @@
 // Player's cards and interaction
 
+use std::ops::{Index, IndexMut};
+
+use serde::{Deserialize, Serialize};
+
 use crate::card::LogicCard;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Player {
     Player,
     Opponent,
 }
 
+impl Player {
+    /// Both seats, in turn order
+    pub const ALL: [Player; 2] = [Player::Player, Player::Opponent];
+
+    /// The seat across the table
+    ///
+    pub fn other(self) -> Player {
+        match self {
+            Player::Player => Player::Opponent,
+            Player::Opponent => Player::Player,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Player::Player => 0,
+            Player::Opponent => 1,
+        }
+    }
+}
+
+/// One of something for each seat at the table, indexed by Player
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Seats<T>([T; 2]);
+
+impl<T> Seats<T> {
+    pub fn new(player: T, opponent: T) -> Self {
+        Self([player, opponent])
+    }
+
+    /// Each seat with its value, in turn order
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = (Player, &T)> {
+        Player::ALL.into_iter().zip(&self.0)
+    }
+}
+
+impl<T> Index<Player> for Seats<T> {
+    type Output = T;
+
+    fn index(&self, seat: Player) -> &T {
+        &self.0[seat.index()]
+    }
+}
+
+impl<T> IndexMut<Player> for Seats<T> {
+    fn index_mut(&mut self, seat: Player) -> &mut T {
+        &mut self.0[seat.index()]
+    }
+}
+
 #[derive(Debug)]
 pub struct PlayerState {
     pub name: String,
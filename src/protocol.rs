@@ -0,0 +1,253 @@
+// Messages exchanged by the two ends of a networked game, and with anyone watching it.
+//
+// Each message is a single line of JSON. Seats are always numbered the way the host
+// sees the table: the host sits in Player's seat and whoever joins sits in Opponent's.
+
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    card::LogicCard,
+    game::{GameAction, GamePhase, GameState, RoundOutcome},
+    player::Player,
+    shuffle::Seed,
+};
+
+/// Bumped whenever a message changes shape or a shuffle deals differently;
+/// both ends must speak the same version
+pub const PROTOCOL_VERSION: u32 = 6;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// First message from each end. The host answers a client's hello with its own,
+    /// then hangs up if their versions differ. A spectator only watches, taking no seat.
+    /// The host's answer to whoever takes the seat carries a token, which they must send
+    /// in their hello to take the seat back after losing the connection
+    Hello {
+        version: u32,
+        name: String,
+        #[serde(default)]
+        spectator: bool,
+        #[serde(default)]
+        token: Option<String>,
+    },
+
+    /// Host to client: the rules the host plays by and the client's side cards.
+    /// The host's own side cards are only counted, they stay hidden until played
+    Rules {
+        target_score: i32,
+        rounds_to_win: usize,
+        hand: Vec<LogicCard>,
+        opponent_hand: usize,
+    },
+
+    /// A move or table action. A client sends one to ask for it, and the host sends every
+    /// action it applies, with the card dealt by a hit or revealed by playing a side card
+    Action {
+        seat: Player,
+        action: GameAction,
+        card: Option<i32>,
+    },
+
+    /// Host to client after each action: hash of the table as both ends can see it
+    StateHash {
+        hash: u64,
+    },
+
+    /// Host to client before each match: the hash of the host's secret seed.
+    /// Sent again with the client's seed once it's agreed, after which cards can be dealt
+    Commit {
+        commitment: String,
+        seed: Option<Seed>,
+    },
+
+    /// Client to host: the client's part of the shuffle, in answer to a commitment
+    Seed {
+        seed: Seed,
+    },
+
+    /// Host to client when a match ends: both seeds, to check the deal against
+    Reveal {
+        host_seed: Seed,
+        client_seed: Seed,
+    },
+
+    Chat {
+        from: String,
+        text: String,
+    },
+
+    /// `seat` gives up the match. Confirmed by the host like any other action
+    Resign {
+        seat: Player,
+    },
+
+    /// Host to spectator on joining: the table as it stands, followed by actions as they happen.
+    /// Side cards are only shown as filled slots
+    Snapshot {
+        seats: Vec<SeatSnapshot>,
+        phase: TablePhase,
+        round_outcome: Option<RoundOutcome>,
+    },
+
+    /// Host to spectator, if the host allows it: the side cards in each hand as they were
+    /// a while ago, so players can't use it to peek
+    Hands {
+        player: Vec<Option<LogicCard>>,
+        opponent: Vec<Option<LogicCard>>,
+    },
+}
+
+/// One seat as a spectator first sees it
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeatSnapshot {
+    pub name: String,
+    pub dealer_row: Vec<i32>,
+    pub played_row: Vec<i32>,
+    pub hand: Vec<bool>, // which side card slots are still filled
+    pub stood: bool,
+    pub bust: bool,
+    pub rounds_won: usize,
+}
+
+/// Where the game is up to, as far as a spectator needs to know
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TablePhase {
+    Turn { seat: Player },
+    RoundEnd,
+    AwaitingNextRound,
+    GameOver { winner: Player },
+}
+
+impl From<&GamePhase> for TablePhase {
+    // The host never thinks or hands over, but a turn is a turn either way
+    fn from(phase: &GamePhase) -> Self {
+        match *phase {
+            GamePhase::Turn { seat } | GamePhase::Thinking { seat, .. } => Self::Turn { seat },
+            GamePhase::Handover { to } => Self::Turn { seat: to },
+            GamePhase::RoundEnd => Self::RoundEnd,
+            GamePhase::AwaitingNextRound => Self::AwaitingNextRound,
+            GamePhase::GameOver { winner } => Self::GameOver { winner },
+        }
+    }
+}
+
+impl From<TablePhase> for GamePhase {
+    fn from(phase: TablePhase) -> Self {
+        match phase {
+            TablePhase::Turn { seat } => Self::Turn { seat },
+            TablePhase::RoundEnd => Self::RoundEnd,
+            TablePhase::AwaitingNextRound => Self::AwaitingNextRound,
+            TablePhase::GameOver { winner } => Self::GameOver { winner },
+        }
+    }
+}
+
+impl Message {
+    /// The message as a line of JSON, ending in a newline
+    ///
+    pub fn to_line(&self) -> String {
+        // Every field serializes to plain JSON, so this can't fail
+        let mut line = serde_json::to_string(self).unwrap_or_default();
+        line.push('\n');
+        line
+    }
+
+    pub fn from_line(line: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(line)
+    }
+
+    /// Everything a spectator can see of the host's table, seats in the host's order
+    ///
+    pub fn snapshot(state: &GameState) -> Self {
+        let seats = Player::ALL
+            .into_iter()
+            .map(|seat| {
+                let side = &state.seats[seat];
+                SeatSnapshot {
+                    name: side.name.clone(),
+                    dealer_row: values(&side.dealer_row),
+                    played_row: values(&side.played_row),
+                    hand: side.hand.iter().map(Option::is_some).collect(),
+                    stood: side.stood,
+                    bust: side.bust,
+                    rounds_won: side.rounds_won,
+                }
+            })
+            .collect();
+
+        Message::Snapshot {
+            seats,
+            phase: TablePhase::from(&state.game_phase),
+            round_outcome: state.round_outcome,
+        }
+    }
+
+    /// Both seats' side cards, for spectators allowed to see them
+    ///
+    pub fn hands(state: &GameState) -> Self {
+        let hand = |seat: Player| state.seats[seat].hand.clone();
+
+        Message::Hands {
+            player: hand(Player::Player),
+            opponent: hand(Player::Opponent),
+        }
+    }
+}
+
+/// Hash of everything on the table that both ends know about, to catch them drifting apart.
+/// `host_seat` is the local seat the host plays from, so both ends hash seats in the same order
+///
+pub fn table_hash(state: &GameState, host_seat: Player) -> u64 {
+    let seat_name = |seat: Player| if seat == host_seat { "host" } else { "guest" };
+    let mut table = String::new();
+
+    for seat in [host_seat, host_seat.other()] {
+        let side = &state.seats[seat];
+        // Only which hand slots are still filled, the host's values are hidden from the client
+        let hand = side
+            .hand
+            .iter()
+            .map(|slot| if slot.is_some() { 'x' } else { '-' })
+            .collect::<String>();
+
+        let _ = write!(
+            table,
+            "{}:{:?}|{:?}|{hand}|{}|{}|{};",
+            seat_name(seat),
+            values(&side.dealer_row),
+            values(&side.played_row),
+            side.stood,
+            side.bust,
+            side.rounds_won,
+        );
+    }
+
+    let _ = match state.game_phase {
+        GamePhase::Turn { seat } | GamePhase::Thinking { seat, .. } => {
+            write!(table, "turn:{}", seat_name(seat))
+        }
+        GamePhase::Handover { to } => write!(table, "handover:{}", seat_name(to)),
+        GamePhase::RoundEnd => write!(table, "round_end"),
+        GamePhase::AwaitingNextRound => write!(table, "next_round"),
+        GamePhase::GameOver { winner } => write!(table, "game_over:{}", seat_name(winner)),
+    };
+
+    fnv1a(table.as_bytes())
+}
+
+fn values(cards: &[LogicCard]) -> Vec<i32> {
+    cards.iter().map(|card| card.value).collect()
+}
+
+// 64 bit FNV-1a, chosen over std's hasher as it gives the same answer on every build
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
@@ -1,31 +1,257 @@
-use crossterm::{QueueableCommand, cursor::MoveTo, style::{Color, SetBackgroundColor}, terminal::{Clear, ClearType}};
-
-use crate::frame::Frame;
-use std::io::{Stdout, Write};
-
-// Only render what changed between last_frame and curr_frame
-// Have the option to force the rendering (only should need once such as at the beginning)
-pub fn render(stdout: &mut Stdout, last_frame: &Frame, curr_frame: &Frame, force: bool) {
-    if force {
-        stdout.queue(SetBackgroundColor(Color::Grey)).unwrap(); 
-        stdout.queue(Clear(ClearType::All)).unwrap();
-        stdout.queue(SetBackgroundColor(Color::Black)).unwrap(); 
-    }
-
-    for (x, col) in curr_frame.iter().enumerate() {
-        for (y, s) in col.iter().enumerate() {
-            // Now we have the x,y index and the actual char at our current frame's location
-
-            // If the character has changed or we're forcing,
-            if *s != last_frame[x][y] || force {
-                // we'll queue up a command to move to the correct location
-                stdout.queue(MoveTo(x as u16, y as u16)).unwrap();
-                // and print single char at the location
-                print!("{}", *s);
+use crossterm::{
+    QueueableCommand,
+    cursor::MoveTo,
+    style::{Attribute, Print, SetAttribute, SetBackgroundColor, SetForegroundColor},
+    terminal::{Clear, ClearType},
+};
+
+use crate::frame::{Frame, Style};
+use std::io::{self, BufWriter, Write};
+
+/// What it took to draw a frame
+///
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct RenderStats {
+    pub cells_changed: usize,
+    pub bytes_written: usize,
+}
+
+/// Somewhere a Frame can be drawn to
+///
+pub trait RenderTarget {
+    /// Draw `curr_frame`, given `last_frame` was drawn before it.
+    /// Forcing redraws everything instead of only what changed.
+    fn render(
+        &mut self,
+        last_frame: &Frame,
+        curr_frame: &Frame,
+        force: bool,
+    ) -> io::Result<RenderStats>;
+}
+
+/// Counts the bytes that make it through to the underlying writer
+///
+struct CountingWriter<W: Write> {
+    inner: W,
+    bytes: usize,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.bytes += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Draws frames to a terminal through crossterm commands.
+/// Everything goes through one buffered handle, so a frame reaches the terminal in a few large writes.
+///
+pub struct Terminal<W: Write> {
+    out: BufWriter<CountingWriter<W>>,
+}
+
+impl<W: Write> Terminal<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out: BufWriter::new(CountingWriter {
+                inner: out,
+                bytes: 0,
+            }),
+        }
+    }
+}
+
+impl<W: Write> RenderTarget for Terminal<W> {
+    // Only render what changed between last_frame and curr_frame
+    // Have the option to force the rendering (only should need once such as at the beginning)
+    //
+    // Changed cells next to each other on a row are written as one run:
+    // a single move to the start, then their text with style changes in between
+    fn render(
+        &mut self,
+        last_frame: &Frame,
+        curr_frame: &Frame,
+        force: bool,
+    ) -> io::Result<RenderStats> {
+        let out = &mut self.out;
+        let mut stats = RenderStats::default();
+
+        // Every cell carries its own colors, so start from the terminal's defaults
+        if force {
+            out.queue(SetAttribute(Attribute::Reset))?;
+            out.queue(Clear(ClearType::All))?;
+        }
+
+        // Style the terminal is currently drawing with, None until we've set one this frame
+        let mut curr_style: Option<Style> = None;
+        // Text of the run being built, all in curr_style
+        let mut run = String::new();
+
+        let num_rows = curr_frame.first().map_or(0, |col| col.len());
+
+        for y in 0..num_rows {
+            // Is the cursor sitting right after the last cell written on this row?
+            let mut in_run = false;
+
+            for (x, col) in curr_frame.iter().enumerate() {
+                let cell = &col[y];
+                let changed = force || last_frame.get(x).and_then(|col| col.get(y)) != Some(cell);
+
+                if !changed {
+                    // end of a run, write out what we have
+                    if in_run {
+                        out.queue(Print(&run))?;
+                        run.clear();
+                        in_run = false;
+                    }
+                    continue;
+                }
+
+                stats.cells_changed += 1;
+
+                // the right half of a wide character is drawn along with its left half
+                if cell.is_continuation() {
+                    continue;
+                }
+
+                // start of a run: move to it once
+                if !in_run {
+                    out.queue(MoveTo(x as u16, y as u16))?;
+                    in_run = true;
+                }
+
+                // switch styles only if this cell differs from the last one drawn
+                if curr_style != Some(cell.style) {
+                    if !run.is_empty() {
+                        out.queue(Print(&run))?;
+                        run.clear();
+                    }
+                    apply_style(out, curr_style, cell.style)?;
+                    curr_style = Some(cell.style);
+                }
+
+                run.push(cell.ch);
             }
+
+            // runs never wrap onto the next row
+            if !run.is_empty() {
+                out.queue(Print(&run))?;
+                run.clear();
+            }
+        }
+
+        // Leave the terminal in its default style
+        if curr_style.is_some() {
+            out.queue(SetAttribute(Attribute::Reset))?;
         }
+
+        // Need to flush at the end since we've queued a bunch of commands
+        let before = out.get_ref().bytes;
+        out.flush()?;
+        stats.bytes_written = out.get_ref().bytes - before;
+
+        Ok(stats)
+    }
+}
+
+/// Keeps the last frame rendered as plain text, for running without a terminal (e.g. tests)
+///
+#[derive(Debug, Default)]
+pub struct TextTarget {
+    text: String,
+}
+
+impl TextTarget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The last frame rendered, one line per row
+    ///
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl RenderTarget for TextTarget {
+    // Text has no notion of changes, so every frame is converted in full
+    fn render(
+        &mut self,
+        last_frame: &Frame,
+        curr_frame: &Frame,
+        force: bool,
+    ) -> io::Result<RenderStats> {
+        let cells_changed = curr_frame
+            .iter()
+            .enumerate()
+            .flat_map(|(x, col)| col.iter().enumerate().map(move |(y, cell)| (x, y, cell)))
+            .filter(|(x, y, cell)| {
+                force || last_frame.get(*x).and_then(|col| col.get(*y)) != Some(cell)
+            })
+            .count();
+
+        self.text = frame_to_text(curr_frame);
+
+        Ok(RenderStats {
+            cells_changed,
+            bytes_written: self.text.len(),
+        })
+    }
+}
+
+/// Characters of a frame without any styling, one line per row with trailing spaces trimmed
+///
+pub fn frame_to_text(frame: &Frame) -> String {
+    let num_rows = frame.first().map_or(0, |col| col.len());
+    let mut text = String::new();
+
+    for y in 0..num_rows {
+        let line: String = frame
+            .iter()
+            .map(|col| col[y])
+            .filter(|cell| !cell.is_continuation())
+            .map(|cell| cell.ch)
+            .collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+
+    text
+}
+
+// Queue the fewest commands needed to go from the `from` style to the `to` style.
+// Attributes can only be cleared all at once, which also clears colors.
+fn apply_style<W: Write>(out: &mut W, from: Option<Style>, to: Style) -> io::Result<()> {
+    let from = match from {
+        Some(from) if from.same_attributes(&to) => from,
+        _ => {
+            out.queue(SetAttribute(Attribute::Reset))?;
+            if to.bold {
+                out.queue(SetAttribute(Attribute::Bold))?;
+            }
+            if to.dim {
+                out.queue(SetAttribute(Attribute::Dim))?;
+            }
+            if to.reverse {
+                out.queue(SetAttribute(Attribute::Reverse))?;
+            }
+            out.queue(SetForegroundColor(to.fg))?;
+            out.queue(SetBackgroundColor(to.bg))?;
+            return Ok(());
+        }
+    };
+
+    if from.fg != to.fg {
+        out.queue(SetForegroundColor(to.fg))?;
+    }
+    if from.bg != to.bg {
+        out.queue(SetBackgroundColor(to.bg))?;
     }
 
-    // Need to flush at the end since we've queued a bunch of commands
-    stdout.flush().unwrap();
+    Ok(())
 }
@@ -1,22 +1,49 @@
-use std::time::Duration;
-
-use crate::{config::Config, frame::Frame, game::GameState, menu::MenuState};
+use crate::{
+    config::Config,
+    frame::Frame,
+    game::GameState,
+    how_to_play::HowToPlayState,
+    menu::MenuState,
+    net::NetSession,
+    settings::{Settings, SettingsState},
+    tutorial::TutorialState,
+};
 
 #[derive(Debug)]
 pub enum Screen {
     StartMenu { menu_state: MenuState },
+    HowToPlay { how_to_play: HowToPlayState },
+    Settings { settings_state: SettingsState },
     InGame { game_state: Box<GameState> },
+    Tutorial { tutorial: Box<TutorialState> },
+    Online { session: Box<NetSession> },
 }
 
 
 impl Screen {
-    pub fn draw(&self, frame: &mut Frame, config: &Config) {
-        // app calls board.draw() so do nothing if InGame
+    /// Name of the screen, for the debug overlay
+    ///
+    pub fn name(&self) -> &'static str {
+        match self {
+            Screen::StartMenu { .. } => "StartMenu",
+            Screen::HowToPlay { .. } => "HowToPlay",
+            Screen::Settings { .. } => "Settings",
+            Screen::InGame { .. } => "InGame",
+            Screen::Tutorial { .. } => "Tutorial",
+            Screen::Online { .. } => "Online",
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame, config: &Config, settings: &Settings) {
+        // app calls board.draw() so do nothing if InGame or in the tutorial
         match self {
-            Screen::StartMenu { menu_state } => menu_state.draw(frame, config),
+            Screen::StartMenu { menu_state } => menu_state.draw(frame, config, settings.theme),
+            Screen::HowToPlay { how_to_play } => how_to_play.draw(frame, config, settings),
+            Screen::Settings { settings_state } => settings_state.draw(frame, config, settings),
             Screen::InGame {
                 game_state: _game_state,
             } => {}
+            Screen::Tutorial { .. } | Screen::Online { .. } => {}
         }
     }
 }
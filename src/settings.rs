@@ -0,0 +1,237 @@
+use crossterm::event::KeyCode;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use std::fmt;
+
+use crate::{
+    card::CardStyle,
+    config::Config,
+    canvas::{Canvas, text_width},
+    frame::Frame,
+    layout::{Align, PageRegions},
+    theme::{Role, Theme},
+};
+
+/// User preferences that apply across every screen
+///
+#[derive(Debug, Clone)]
+pub struct Settings {
+    pub theme: Theme,
+    pub card_style: CardStyle,
+    pub animations: bool,
+    pub opponent: Opponent, // who Start Game plays against
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Self {
+            theme: Theme::from_env(),
+            card_style: CardStyle::from_env(),
+            animations: true,
+            opponent: Opponent::Computer,
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Who sits across the table in a one player game
+///
+#[derive(EnumIter, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Opponent {
+    Computer,
+    Bot, // the program given with --bot
+}
+
+#[derive(EnumIter, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SettingItem {
+    Theme,
+    CardStyle,
+    Animations,
+    Opponent,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum SettingsAction {
+    SelectionUp,
+    SelectionDown,
+    NextValue,
+    PreviousValue,
+    Back,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum SettingsEvent {
+    Exit,
+}
+
+#[derive(Debug)]
+pub struct SettingsState {
+    selected: SettingItem,
+}
+
+impl SettingsState {
+    pub fn new() -> Self {
+        Self {
+            selected: SettingItem::Theme,
+        }
+    }
+
+    pub fn handle_input(&mut self, key: KeyCode) -> Option<SettingsAction> {
+        self.action_from_key(key)
+    }
+
+    /// Convert a key pressed into an Action
+    ///
+    pub fn action_from_key(&self, key: KeyCode) -> Option<SettingsAction> {
+        match key {
+            KeyCode::Up => Some(SettingsAction::SelectionUp),
+            KeyCode::Down => Some(SettingsAction::SelectionDown),
+            KeyCode::Right | KeyCode::Enter => Some(SettingsAction::NextValue),
+            KeyCode::Left => Some(SettingsAction::PreviousValue),
+            KeyCode::Esc | KeyCode::Backspace => Some(SettingsAction::Back),
+            KeyCode::Char(c) => match c {
+                'w' => Some(SettingsAction::SelectionUp),
+                's' => Some(SettingsAction::SelectionDown),
+                'd' | ' ' => Some(SettingsAction::NextValue),
+                'a' => Some(SettingsAction::PreviousValue),
+                'x' => Some(SettingsAction::Back),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    pub fn apply_action(
+        &mut self,
+        action: SettingsAction,
+        settings: &mut Settings,
+    ) -> Option<SettingsEvent> {
+        match action {
+            SettingsAction::SelectionUp => {
+                self.selected = step(self.selected, -1);
+                None
+            }
+            SettingsAction::SelectionDown => {
+                self.selected = step(self.selected, 1);
+                None
+            }
+            SettingsAction::NextValue => {
+                self.change_value(settings, 1);
+                None
+            }
+            SettingsAction::PreviousValue => {
+                self.change_value(settings, -1);
+                None
+            }
+            SettingsAction::Back => Some(SettingsEvent::Exit),
+        }
+    }
+
+    /// Cycle the selected setting forwards or backwards through its values
+    ///
+    fn change_value(&self, settings: &mut Settings, direction: isize) {
+        match self.selected {
+            SettingItem::Theme => settings.theme = step(settings.theme, direction),
+            SettingItem::CardStyle => settings.card_style = step(settings.card_style, direction),
+            SettingItem::Animations => settings.animations = !settings.animations,
+            SettingItem::Opponent => settings.opponent = step(settings.opponent, direction),
+        }
+    }
+
+    /// Draw Text Helper
+    ///
+    /// Characters that would land outside the frame are dropped
+    fn draw_text(
+        &self,
+        text: &str,
+        x: usize,
+        y: usize,
+        role: Role,
+        theme: Theme,
+        frame: &mut Frame,
+    ) {
+        Canvas::new(frame).text(text, x, y, theme.style(role));
+    }
+
+    /// Draw each setting with its current value, one per line
+    ///
+    pub fn draw(&self, frame: &mut Frame, config: &Config, settings: &Settings) {
+        let theme = settings.theme;
+        let regions = PageRegions::new(config, 0);
+
+        let title = "SETTINGS";
+        let x = regions.title.align_x(text_width(title), Align::Center);
+        self.draw_text(title, x, regions.title.y, Role::Title, theme, frame);
+
+        let mut y = regions.body.y + 2;
+
+        for item in SettingItem::iter() {
+            let value = match item {
+                SettingItem::Theme => settings.theme.to_string(),
+                SettingItem::CardStyle => settings.card_style.to_string(),
+                SettingItem::Animations => match settings.animations {
+                    true => "On".to_string(),
+                    false => "Off".to_string(),
+                },
+                SettingItem::Opponent => settings.opponent.to_string(),
+            };
+
+            let (line, role) = if self.selected == item {
+                (format!("{item}:  < {value} >"), Role::Highlight)
+            } else {
+                (format!("{item}:    {value}  "), Role::Text)
+            };
+            let x = regions.body.align_x(text_width(&line), Align::Center);
+            self.draw_text(&line, x, y, role, theme, frame);
+            y += 2;
+        }
+
+        let footer = "w/s: select   a/d: change   x: back to menu";
+        let x = regions.footer.align_x(text_width(footer), Align::Center);
+        self.draw_text(footer, x, regions.footer.y, Role::Divider, theme, frame);
+    }
+}
+
+impl Default for SettingsState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Move `steps` places through an enum's variants, wrapping around at either end
+///
+fn step<T: IntoEnumIterator + PartialEq + Copy>(current: T, steps: isize) -> T {
+    let items: Vec<T> = T::iter().collect();
+    let index = items.iter().position(|item| *item == current).unwrap_or(0);
+    let len = items.len() as isize;
+
+    items[(index as isize + steps).rem_euclid(len) as usize]
+}
+
+/// Implement display for SettingItem enum to turn variants into labels
+///
+impl fmt::Display for SettingItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SettingItem::Theme => write!(f, "Theme"),
+            SettingItem::CardStyle => write!(f, "Card Style"),
+            SettingItem::Animations => write!(f, "Animations"),
+            SettingItem::Opponent => write!(f, "Opponent"),
+        }
+    }
+}
+
+impl fmt::Display for Opponent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Opponent::Computer => write!(f, "Computer"),
+            Opponent::Bot => write!(f, "Bot"),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// Commit-reveal shuffles, so the host of a networked game can't stack the deck.
+//
+// Before each match the host picks a secret seed and sends only its SHA-256 hash, the
+// commitment. The client answers with a seed of its own, and the deck is seeded from both.
+// The host can't steer the deal, having committed before seeing the client's seed, and the
+// client can't predict it without the host's secret. Once the match is over the host
+// reveals its seed and the client deals the deck again to check every card it was shown.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::deck::{DeckSource, RandomDeck};
+
+/// One side's contribution to a match's shuffle: 32 random bytes, sent as hex
+///
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Seed([u8; 32]);
+
+impl Seed {
+    pub fn random() -> Self {
+        Self(rand::random())
+    }
+
+    /// Hash of the seed, which commits to it without giving it away
+    ///
+    pub fn commitment(&self) -> String {
+        to_hex(&Sha256::digest(self.0))
+    }
+}
+
+// Kept out of logs and debug output, as a host's seed is a secret until the match ends
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Seed(..)")
+    }
+}
+
+impl From<Seed> for String {
+    fn from(seed: Seed) -> String {
+        to_hex(&seed.0)
+    }
+}
+
+impl TryFrom<String> for Seed {
+    type Error = String;
+
+    fn try_from(hex: String) -> Result<Self, String> {
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                hex.get(i..i + 2)
+                    .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| format!("seed isn't hex: {hex}"))?;
+
+        bytes
+            .try_into()
+            .map(Self)
+            .map_err(|_| format!("seed isn't 32 bytes: {hex}"))
+    }
+}
+
+/// Seed for a match's deck, mixed from both sides' seeds
+///
+pub fn deck_seed(host: &Seed, client: &Seed) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(host.0)
+        .chain_update(client.0)
+        .finalize()
+        .into()
+}
+
+/// The deck a match is dealt from
+///
+pub fn deck(host: &Seed, client: &Seed) -> RandomDeck {
+    RandomDeck::with_seed(deck_seed(host, client))
+}
+
+/// Check a revealed host seed against its commitment, then that dealing again from both seeds
+/// gives exactly the dealer cards that were shown, in order
+///
+pub fn verify_deal(
+    commitment: &str,
+    host: &Seed,
+    client: &Seed,
+    dealt: &[i32],
+) -> Result<(), String> {
+    if host.commitment() != commitment {
+        return Err("the revealed seed doesn't match the host's commitment".to_string());
+    }
+
+    let mut deck = deck(host, client);
+    for (i, &shown) in dealt.iter().enumerate() {
+        let value = deck.deal().value;
+        if value != shown {
+            return Err(format!(
+                "dealer card {} was {shown} but the shuffle deals {value}",
+                i + 1
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Bytes as lowercase hex, two digits each
+///
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
@@ -0,0 +1,138 @@
+// Color themes
+//
+// Drawing code asks for a semantic Role and the active Theme decides what it looks like.
+
+use std::{env, fmt};
+
+use crossterm::style::Color;
+use strum_macros::EnumIter;
+
+use crate::frame::Style;
+
+/// What a piece of the screen is, rather than how it should look
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Role {
+    Text,          // regular text
+    Title,         // headings and title art
+    Divider,       // lines separating parts of the screen
+    CardBorder,    // cards with no kind, e.g. hidden cards
+    DealerCard,    // dealer cards
+    PlusCard,      // side cards that add to the score
+    MinusCard,     // side cards that subtract from the score
+    PlusMinusCard, // side cards that can be flipped
+    Warning,       // score close to busting
+    Danger,        // busted score
+    Highlight,     // selected menu items
+}
+
+#[derive(EnumIter, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Theme {
+    Classic,
+    Dark,
+    HighContrast,
+    Monochrome,
+}
+
+impl Theme {
+    /// Default theme, honoring the NO_COLOR convention (https://no-color.org)
+    ///
+    pub fn from_env() -> Self {
+        match env::var_os("NO_COLOR") {
+            Some(value) if !value.is_empty() => Theme::Monochrome,
+            _ => Theme::Classic,
+        }
+    }
+
+    /// Look up the style for a role in this theme
+    ///
+    pub fn style(&self, role: Role) -> Style {
+        match self {
+            Theme::Classic => Self::classic(role),
+            Theme::Dark => Self::dark(role),
+            Theme::HighContrast => Self::high_contrast(role),
+            Theme::Monochrome => Self::monochrome(role),
+        }
+    }
+
+    fn classic(role: Role) -> Style {
+        let base = Style::new().fg(Color::White).bg(Color::Black);
+
+        match role {
+            Role::Text | Role::Title | Role::CardBorder => base,
+            Role::Divider => base.dim(),
+            Role::DealerCard => base.fg(Color::Green),
+            Role::PlusCard => base.fg(Color::Blue),
+            Role::MinusCard => base.fg(Color::Red),
+            Role::PlusMinusCard => base.fg(Color::Yellow),
+            Role::Warning => base.fg(Color::Red),
+            Role::Danger => base.fg(Color::Red).bold(),
+            Role::Highlight => base.reverse().bold(),
+        }
+    }
+
+    fn dark(role: Role) -> Style {
+        let base = Style::new().fg(Color::Grey).bg(Color::Black);
+
+        match role {
+            Role::Text | Role::CardBorder => base,
+            Role::Title => base.fg(Color::DarkCyan),
+            Role::Divider => base.fg(Color::DarkGrey),
+            Role::DealerCard => base.fg(Color::DarkGreen),
+            Role::PlusCard => base.fg(Color::DarkCyan),
+            Role::MinusCard => base.fg(Color::DarkRed),
+            Role::PlusMinusCard => base.fg(Color::DarkYellow),
+            Role::Warning => base.fg(Color::DarkYellow),
+            Role::Danger => base.fg(Color::Red).bold(),
+            Role::Highlight => base.fg(Color::Black).bg(Color::DarkCyan),
+        }
+    }
+
+    fn high_contrast(role: Role) -> Style {
+        let base = Style::new().fg(Color::White).bg(Color::Black).bold();
+
+        match role {
+            Role::Text | Role::Title | Role::CardBorder | Role::Divider => base,
+            Role::DealerCard => base.fg(Color::Green),
+            Role::PlusCard => base.fg(Color::Cyan),
+            Role::MinusCard => base.fg(Color::Magenta),
+            Role::PlusMinusCard => base.fg(Color::Yellow),
+            Role::Warning => base.fg(Color::Yellow),
+            Role::Danger => base.fg(Color::Red).reverse(),
+            Role::Highlight => base.fg(Color::Black).bg(Color::Yellow),
+        }
+    }
+
+    // No colors at all, only the terminal's own foreground/background and attributes
+    fn monochrome(role: Role) -> Style {
+        let base = Style::new().fg(Color::Reset).bg(Color::Reset);
+
+        match role {
+            Role::Text | Role::Title | Role::CardBorder => base,
+            Role::Divider => base.dim(),
+            Role::DealerCard | Role::PlusCard | Role::MinusCard | Role::PlusMinusCard => base,
+            Role::Warning => base.bold(),
+            Role::Danger => base.bold().reverse(),
+            Role::Highlight => base.reverse(),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+/// Implement display for Theme enum to show theme names in settings
+///
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Theme::Classic => write!(f, "Classic"),
+            Theme::Dark => write!(f, "Dark"),
+            Theme::HighContrast => write!(f, "High Contrast"),
+            Theme::Monochrome => write!(f, "Monochrome"),
+        }
+    }
+}
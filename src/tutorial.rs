@@ -0,0 +1,176 @@
+// Scripted tutorial match
+//
+// The deck and the opponent's moves are fixed so the prompts can talk about
+// exact scores. Each step expects one specific action from the player; any
+// other action is blocked and explained.
+
+use crate::{
+    card::LogicCard,
+    deck::ScriptedDeck,
+    game::{GameAction, GamePhase, GameState},
+    player::Player,
+};
+
+/// Dealer cards in the order they are drawn, by either player
+const TUTORIAL_DECK: &[i32] = &[8, 7, 6, 9, 2, 10, 10, 7, 5, 9];
+
+/// Opponent moves in the order they are made
+const TUTORIAL_OPPONENT_MOVES: &[GameAction] = &[
+    // Round 1: draw twice, draw once more after the player reaches 20, then stand on 18
+    GameAction::Hit,
+    GameAction::Hit,
+    GameAction::Hit,
+    GameAction::Stand,
+    // Round 2: keep drawing until busting
+    GameAction::Hit,
+    GameAction::Hit,
+    GameAction::Hit,
+];
+
+struct TutorialStep {
+    prompt: &'static str,
+    expected: GameAction,
+    // Explanation shown when the player tries anything else
+    hint: &'static str,
+}
+
+const STEPS: &[TutorialStep] = &[
+    TutorialStep {
+        prompt: "Welcome to Kaazap! Get closer to 20 than your opponent without going over. \
+                 Press 'd' to draw your first dealer card.",
+        expected: GameAction::Hit,
+        hint: "Every turn starts by drawing a dealer card. Press 'd'.",
+    },
+    TutorialStep {
+        prompt: "You drew an 8. The opponent draws on their turn too, their cards are on the \
+                 right. Press 'd' to draw again.",
+        expected: GameAction::Hit,
+        hint: "8 is a long way from 20, so keep drawing with 'd'.",
+    },
+    TutorialStep {
+        prompt: "You're on 14 with a 6 in hand - play it now with '1' to hit exactly 20.",
+        expected: GameAction::PlayHand { index: 0 },
+        hint: "Drawing could take you over 20. Your first side card is a 6: press '1'.",
+    },
+    TutorialStep {
+        prompt: "20 stands for you automatically. Watch the opponent finish, then press 'n' \
+                 to deal the next round.",
+        expected: GameAction::NextRound,
+        hint: "You're done for this round. Press 'n' once the round is over.",
+    },
+    TutorialStep {
+        prompt: "Round two. Side cards don't come back, so you have three left. Press 'd' to \
+                 draw.",
+        expected: GameAction::Hit,
+        hint: "Start the round by drawing a dealer card with 'd'.",
+    },
+    TutorialStep {
+        prompt: "You're on 10. Press 'd' to draw again.",
+        expected: GameAction::Hit,
+        hint: "None of your side cards get you to 20 from here. Press 'd'.",
+    },
+    TutorialStep {
+        prompt: "You're on 17, and every side card you have left would take you over 20. \
+                 Another dealer card will most likely bust you too, so press 's' to stand.",
+        expected: GameAction::Stand,
+        hint: "Your side cards are all too big and a dealer card will likely take you over 20. \
+               Stand with 's'.",
+    },
+    TutorialStep {
+        prompt: "The opponent had to chase your 17 and went over 20: busted! Press 'n' to \
+                 continue.",
+        expected: GameAction::NextRound,
+        hint: "Press 'n' once the round is over.",
+    },
+];
+
+const TUTORIAL_COMPLETE: &str = "Tutorial complete! Keep playing until someone wins 3 rounds, \
+                                 or press 'x' to return to the menu.";
+
+#[derive(Debug)]
+pub struct TutorialState {
+    pub game_state: Box<GameState>,
+    step: usize,
+    notice: Option<&'static str>,
+}
+
+impl TutorialState {
+    pub fn new() -> Self {
+        let mut game_state = GameState::with_script(
+            Box::new(ScriptedDeck::new(TUTORIAL_DECK)),
+            TUTORIAL_OPPONENT_MOVES.to_vec(),
+        );
+
+        game_state.seats[Player::Player].name = "Rookie".to_string();
+        // Only the 6 fits round one; the rest are too big to help on 17 in round two
+        game_state.seats[Player::Player].hand = [6, 4, 5, 5]
+            .into_iter()
+            .map(|value| Some(LogicCard::new(value)))
+            .collect();
+
+        Self {
+            game_state: Box::new(game_state),
+            step: 0,
+            notice: None,
+        }
+    }
+
+    /// Only pass on the action the current step expects, explaining anything else.
+    /// Once every step is done the game plays normally.
+    ///
+    pub fn handle_game_input(&mut self, key: char) {
+        let Some(action) = self.game_state.handle_game_input(key) else {
+            return;
+        };
+
+        let Some(step) = STEPS.get(self.step) else {
+            self.game_state.apply_game_action(action);
+            return;
+        };
+
+        if matches!(
+            self.game_state.game_phase,
+            GamePhase::Thinking { .. }
+                | GamePhase::Turn {
+                    seat: Player::Opponent
+                }
+        ) {
+            self.notice = Some("Wait for the opponent to finish their turn.");
+        } else if action != step.expected {
+            self.notice = Some(step.hint);
+        } else if step.expected == GameAction::NextRound
+            && !matches!(self.game_state.game_phase, GamePhase::AwaitingNextRound)
+        {
+            self.notice = Some("The round isn't over yet, wait for the opponent.");
+        } else {
+            self.game_state.apply_game_action(action);
+            self.step += 1;
+            self.notice = None;
+        }
+    }
+
+    pub fn update(&mut self) -> bool {
+        self.game_state.update()
+    }
+
+    /// Text for the board overlay: the current prompt plus any blocked-move explanation
+    ///
+    pub fn overlay_text(&self) -> Vec<String> {
+        let prompt = STEPS
+            .get(self.step)
+            .map_or(TUTORIAL_COMPLETE, |step| step.prompt);
+
+        let mut paragraphs = vec![prompt.to_string()];
+        if let Some(notice) = self.notice {
+            paragraphs.push(format!("Not now: {notice}"));
+        }
+
+        paragraphs
+    }
+}
+
+impl Default for TutorialState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
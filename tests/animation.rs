@@ -0,0 +1,50 @@
+// Card animations, started by comparing the game state between ticks
+
+use std::time::Duration;
+
+use kaazap::{
+    animation::{Animations, Motion},
+    deck::ScriptedDeck,
+    game::{GameAction, GamePhase, GameState},
+    player::Player,
+};
+
+// How the first side card `seat` plays starts moving
+fn play_motion(mut state: GameState, seat: Player) -> Motion {
+    let mut animations = Animations::new();
+    animations.update(&state, Duration::ZERO);
+
+    state.game_phase = GamePhase::Turn { seat };
+    assert!(state.apply_seat_action(seat, GameAction::PlayHand { index: 1 }));
+    animations.update(&state, Duration::ZERO);
+
+    animations.played(seat, 0).unwrap().motion
+}
+
+#[test]
+fn own_cards_flip_in_place() {
+    assert_eq!(play_motion(GameState::new(), Player::Player), Motion::Flip);
+}
+
+#[test]
+fn computer_cards_come_out_of_the_hidden_hand() {
+    assert_eq!(
+        play_motion(GameState::new(), Player::Opponent),
+        Motion::Reveal { hand_index: 1 }
+    );
+}
+
+#[test]
+fn hot_seat_cards_flip_for_whoever_is_playing() {
+    for seat in Player::ALL {
+        assert_eq!(play_motion(GameState::hot_seat(), seat), Motion::Flip);
+    }
+}
+
+#[test]
+fn spectators_see_both_hands_revealed() {
+    for seat in Player::ALL {
+        let state = GameState::spectated(Box::new(ScriptedDeck::new(&[])));
+        assert_eq!(play_motion(state, seat), Motion::Reveal { hand_index: 1 });
+    }
+}
@@ -0,0 +1,122 @@
+// Sound cues, checked by playing the app into a recording backend
+
+use std::time::Duration;
+
+use strum::IntoEnumIterator;
+
+use crossterm::event::KeyCode;
+use kaazap::{
+    app::App,
+    audio::{self, Cue, RecordingBackend},
+    card::{CardStyle, LogicCard},
+    config::Config,
+    deck::ScriptedDeck,
+    game::GameState,
+    player::Player,
+    settings::{Opponent, Settings},
+    theme::Theme,
+};
+
+fn app_with_recorder() -> (App, RecordingBackend) {
+    let settings = Settings {
+        theme: Theme::Monochrome,
+        card_style: CardStyle::Ascii,
+        animations: false,
+        opponent: Opponent::Computer,
+    };
+    let recorder = RecordingBackend::new();
+    let app = App::with_audio(
+        Config::from_size(100, 30),
+        settings,
+        Box::new(recorder.clone()),
+    );
+
+    (app, recorder)
+}
+
+fn cards(values: &[i32]) -> Vec<LogicCard> {
+    values.iter().map(|&value| LogicCard::new(value)).collect()
+}
+
+// Key press followed by a tick, like the game loop
+fn press(app: &mut App, c: char) {
+    app.handle_key(KeyCode::Char(c));
+    app.tick(Duration::from_millis(16));
+}
+
+// Player on 18 with the opponent already stood, so the next card decides the round
+fn player_to_draw(next_card: i32) -> GameState {
+    let mut state = GameState::with_script(Box::new(ScriptedDeck::new(&[next_card])), vec![]);
+    state.seats[Player::Player].dealer_row = cards(&[10, 8]);
+    state.seats[Player::Opponent].dealer_row = cards(&[10, 7]);
+    state.seats[Player::Opponent].stood = true;
+    state
+}
+
+#[test]
+fn menu_plays_move_and_select() {
+    let (mut app, recorder) = app_with_recorder();
+
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Up);
+    app.handle_key(KeyCode::Enter);
+
+    assert_eq!(
+        recorder.played(),
+        [Cue::MenuMove, Cue::MenuMove, Cue::MenuSelect]
+    );
+}
+
+#[test]
+fn busting_plays_the_bust_cue_once() {
+    let (mut app, recorder) = app_with_recorder();
+    app.start_game(player_to_draw(5));
+
+    press(&mut app, 'd');
+    // Ticking on afterwards mustn't play it again
+    for _ in 0..5 {
+        app.tick(Duration::from_millis(16));
+    }
+
+    assert_eq!(recorder.count(Cue::Bust), 1);
+    assert_eq!(
+        recorder.played(),
+        [Cue::CardDealt, Cue::Bust, Cue::RoundLost]
+    );
+}
+
+#[test]
+fn winning_the_match_plays_round_and_match_cues() {
+    let (mut app, recorder) = app_with_recorder();
+    let mut state = player_to_draw(1);
+    state.seats[Player::Player].rounds_won = 2;
+    app.start_game(state);
+
+    press(&mut app, 'd');
+    press(&mut app, 's');
+
+    assert_eq!(
+        recorder.played(),
+        [Cue::CardDealt, Cue::Stand, Cue::RoundWon, Cue::MatchWon]
+    );
+}
+
+#[test]
+fn leaving_a_game_plays_nothing_more() {
+    let (mut app, recorder) = app_with_recorder();
+    app.start_game(player_to_draw(5));
+
+    press(&mut app, 'x');
+    app.tick(Duration::from_millis(16));
+
+    assert!(recorder.played().is_empty());
+}
+
+#[test]
+fn every_cue_has_a_shipped_sound() {
+    let sounds = audio::assets_dir().join("sounds");
+    for cue in Cue::iter() {
+        let path = sounds.join(cue.file_stem()).with_extension("wav");
+        assert!(path.is_file(), "{} is missing", path.display());
+    }
+}
@@ -0,0 +1,239 @@
+// External bots, played by small shell scripts
+
+use std::{
+    env, fs,
+    path::PathBuf,
+    process, thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::KeyCode;
+use kaazap::{
+    app::App,
+    bot::{self, Bot},
+    config::Config,
+    game::{GameAction, GamePhase, GameState},
+    player::Player,
+    settings::Settings,
+};
+
+// Reads the turn number out of the line in `$line` into `$turn`
+const READ_TURN: &str = r#"turn=${line#*\"turn\":}; turn=${turn%%,*}"#;
+
+// Write a bot script answering each line it's sent with `answer`, which can use `$turn`,
+// returning the command to run it
+fn script(name: &str, answer: &str) -> String {
+    script_of(
+        name,
+        &format!("while read line; do {READ_TURN}; {answer}; done\n"),
+    )
+}
+
+// Write a bot script that's just `body`, returning the command to run it
+fn script_of(name: &str, body: &str) -> String {
+    let dir = env::temp_dir().join(format!("kaazap-bots-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path: PathBuf = dir.join(format!("{name}.sh"));
+    fs::write(&path, body).unwrap();
+
+    format!("sh {}", path.display())
+}
+
+// Stand for the player, then let the bot play until it stands too or `limit` passes
+fn play_out_bot_turns(bot: Bot, limit: Duration) -> GameState {
+    let mut state = GameState::against_bot(bot);
+    assert!(state.apply_game_action(GameAction::Stand));
+
+    let started = Instant::now();
+    while !state.seats[Player::Opponent].stood {
+        assert!(started.elapsed() < limit, "bot didn't stand in time");
+        state.update();
+        thread::sleep(Duration::from_millis(5));
+    }
+    state
+}
+
+#[test]
+fn bot_moves_are_played_as_soon_as_they_arrive() {
+    // Plays its first side card, then can't play it again so stands
+    let bot = Bot::spawn(&script("first_card", r#"echo "$turn play 1""#))
+        .unwrap()
+        .with_timeout(Duration::from_secs(5));
+    assert_eq!(bot.name(), "first_card");
+
+    let state = play_out_bot_turns(bot, Duration::from_secs(3));
+    let side = &state.seats[Player::Opponent];
+
+    assert_eq!(side.name, "first_card");
+    assert_eq!(side.played_row.len(), 1);
+    assert!(side.hand[0].is_none());
+    assert!(side.dealer_row.is_empty());
+}
+
+#[test]
+fn slow_bot_stands() {
+    let bot = Bot::spawn(&script("slow", r#"sleep 5; echo "$turn hit""#))
+        .unwrap()
+        .with_timeout(Duration::from_millis(100));
+
+    let state = play_out_bot_turns(bot, Duration::from_secs(2));
+
+    assert!(state.seats[Player::Opponent].dealer_row.is_empty());
+    assert!(!matches!(state.game_phase, GamePhase::Thinking { .. }));
+}
+
+#[test]
+fn bot_that_answers_nonsense_stands() {
+    let bot = Bot::spawn(&script("nonsense", r#"echo "$turn fold""#))
+        .unwrap()
+        .with_timeout(Duration::from_secs(5));
+
+    let state = play_out_bot_turns(bot, Duration::from_secs(3));
+
+    assert!(state.seats[Player::Opponent].dealer_row.is_empty());
+    assert!(state.seats[Player::Opponent].played_row.is_empty());
+}
+
+#[test]
+fn answer_without_the_turn_number_stands() {
+    let bot = Bot::spawn(&script("unnumbered", "echo hit"))
+        .unwrap()
+        .with_timeout(Duration::from_millis(300));
+
+    let state = play_out_bot_turns(bot, Duration::from_secs(2));
+
+    assert!(state.seats[Player::Opponent].dealer_row.is_empty());
+}
+
+#[test]
+fn late_answer_is_not_taken_for_the_next_turn() {
+    // Misses the timeout on its first turn with a hit, then stands on time
+    let body = format!(
+        "read line; {READ_TURN}; sleep 1.5; echo \"$turn hit\"\n\
+         while read line; do {READ_TURN}; echo \"$turn stand\"; done\n"
+    );
+    let bot = Bot::spawn(&script_of("late", &body))
+        .unwrap()
+        .with_timeout(Duration::from_secs(1));
+
+    // The first round ends with the bot timed out; the second starts while the hit is on its way
+    let mut state = play_out_bot_turns(bot, Duration::from_secs(3));
+    let started = Instant::now();
+    while !matches!(state.game_phase, GamePhase::AwaitingNextRound) {
+        assert!(
+            started.elapsed() < Duration::from_secs(3),
+            "round didn't end"
+        );
+        state.update();
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(state.apply_game_action(GameAction::NextRound));
+    assert!(state.apply_game_action(GameAction::Stand));
+
+    let started = Instant::now();
+    while !state.seats[Player::Opponent].stood {
+        assert!(
+            started.elapsed() < Duration::from_secs(3),
+            "bot didn't stand in time"
+        );
+        state.update();
+        thread::sleep(Duration::from_millis(5));
+    }
+
+    assert!(state.seats[Player::Opponent].dealer_row.is_empty());
+}
+
+#[test]
+fn bot_that_never_reads_does_not_hold_up_the_game() {
+    let bot = Bot::spawn(&script_of("deaf", "sleep 30\n")).unwrap();
+    let state = GameState::new();
+
+    // Far more than a pipe holds
+    let started = Instant::now();
+    for _ in 0..5000 {
+        bot.ask(&state, Player::Opponent);
+    }
+
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert!(!bot.has_answered());
+}
+
+// Name at the opponent's seat of a game started from the menu, after pressing `keys`
+fn opponent_after(app: &mut App, keys: &[KeyCode]) -> String {
+    for &key in keys.iter().chain(&[KeyCode::Enter]) {
+        app.handle_key(key);
+    }
+    app.game_state().unwrap().seats[Player::Opponent]
+        .name
+        .clone()
+}
+
+// Settings is last in the menu and Opponent the last setting, so up from the top reaches both
+const SWITCH_OPPONENT: [KeyCode; 5] = [
+    KeyCode::Up,
+    KeyCode::Enter,
+    KeyCode::Up,
+    KeyCode::Right,
+    KeyCode::Esc,
+];
+
+#[test]
+fn opponent_is_picked_in_settings() {
+    let config = Config::from_size(100, 30);
+    let bot = Bot::spawn(&script("picked", r#"echo "$turn stand""#)).unwrap();
+
+    let mut app = App::with_settings(config.clone(), Settings::new());
+    app.use_bot(bot.clone());
+    assert_eq!(opponent_after(&mut app, &[]), "picked");
+
+    let mut app = App::with_settings(config.clone(), Settings::new());
+    app.use_bot(bot);
+    assert_eq!(opponent_after(&mut app, &SWITCH_OPPONENT), "Opponent");
+
+    // Without a bot there's only the computer to pick
+    let mut app = App::with_settings(config, Settings::new());
+    assert_eq!(opponent_after(&mut app, &SWITCH_OPPONENT), "Opponent");
+}
+
+#[test]
+fn bots_are_named_after_their_program_or_script() {
+    assert_eq!(bot::name_of("./mybot"), "mybot");
+    assert_eq!(bot::name_of("python3 bot.py --level 3"), "bot");
+    assert_eq!(bot::name_of("python3 -u bot.py"), "bot");
+    assert_eq!(bot::name_of("/usr/bin/env node ai.js 3"), "ai");
+    assert_eq!(bot::name_of("java -jar bot.jar"), "bot");
+    assert_eq!(bot::name_of("python3"), "Bot");
+}
+
+#[test]
+fn missing_program_fails_to_start() {
+    assert!(Bot::spawn("/no/such/kaazap-bot").is_err());
+    assert!(Bot::spawn("").is_err());
+}
+
+#[test]
+fn bot_sees_only_its_own_side_cards() {
+    let state = GameState::new();
+    let line: serde_json::Value =
+        serde_json::from_str(&bot::state_line(&state, Player::Opponent, 7)).unwrap();
+
+    assert_eq!(line["turn"], 7);
+    assert_eq!(line["target_score"], 20);
+    assert_eq!(line["you"]["name"], "Opponent");
+    assert_eq!(line["you"]["hand"], serde_json::json!([2, 6, 1, 4]));
+    assert_eq!(line["opponent"]["cards_in_hand"], 4);
+    assert!(line["opponent"].get("hand").is_none());
+}
+
+#[test]
+fn moves_are_read_from_words() {
+    assert_eq!(bot::parse_action("hit"), Some(GameAction::Hit));
+    assert_eq!(bot::parse_action(" stand \n"), Some(GameAction::Stand));
+    assert_eq!(
+        bot::parse_action("play 2"),
+        Some(GameAction::PlayHand { index: 1 })
+    );
+    assert_eq!(bot::parse_action("play 0"), None);
+    assert_eq!(bot::parse_action("play"), None);
+    assert_eq!(bot::parse_action("next"), None);
+}
@@ -0,0 +1,95 @@
+// The chat line and its quick emotes
+
+use crossterm::event::KeyCode;
+use kaazap::{
+    CHAT_MAX_LEN,
+    chat::{self, Chat, ChatAction},
+};
+
+// Type `keys` into the chat, returning whatever was said
+fn type_keys(chat: &mut Chat, keys: &[KeyCode]) -> Vec<String> {
+    let mut said = vec![];
+    for &key in keys {
+        if let Some(action) = chat.handle_input(key)
+            && let Some(message) = chat.apply_action(action)
+        {
+            said.push(message);
+        }
+    }
+    said
+}
+
+fn chars(text: &str) -> Vec<KeyCode> {
+    text.chars().map(KeyCode::Char).collect()
+}
+
+#[test]
+fn only_t_opens_the_chat_line() {
+    let chat = Chat::new();
+
+    assert_eq!(chat.handle_input(KeyCode::Char('d')), None);
+    assert_eq!(chat.handle_input(KeyCode::Enter), None);
+    assert_eq!(
+        chat.handle_input(KeyCode::Char('t')),
+        Some(ChatAction::Open)
+    );
+}
+
+#[test]
+fn open_chat_line_takes_game_keys_as_text() {
+    let mut chat = Chat::new();
+    let mut keys = chars("tds q");
+    keys.push(KeyCode::Backspace);
+    keys.push(KeyCode::Char('g'));
+
+    assert!(type_keys(&mut chat, &keys).is_empty());
+    assert!(chat.is_open());
+    assert_eq!(chat.input(), Some("ds g"));
+
+    assert_eq!(type_keys(&mut chat, &[KeyCode::Enter]), ["ds g"]);
+    assert!(!chat.is_open());
+}
+
+#[test]
+fn escape_closes_the_line_without_saying_anything() {
+    let mut chat = Chat::new();
+    let mut keys = chars("thello");
+    keys.push(KeyCode::Esc);
+
+    assert!(type_keys(&mut chat, &keys).is_empty());
+    assert!(!chat.is_open());
+}
+
+#[test]
+fn numbers_pick_quick_emotes() {
+    assert_eq!(chat::message("1").as_deref(), Some("Nice hand"));
+    assert_eq!(chat::message("2").as_deref(), Some("Ouch"));
+    // Anything else is said as typed
+    assert_eq!(chat::message("0").as_deref(), Some("0"));
+    assert_eq!(chat::message("99").as_deref(), Some("99"));
+    assert_eq!(chat::message("2 more").as_deref(), Some("2 more"));
+    assert_eq!(chat::message(""), None);
+}
+
+#[test]
+fn messages_stop_at_the_length_limit() {
+    let mut chat = Chat::new();
+    type_keys(
+        &mut chat,
+        &chars(&format!("t{}", "a".repeat(CHAT_MAX_LEN + 10))),
+    );
+
+    assert_eq!(chat.input().unwrap().len(), CHAT_MAX_LEN);
+}
+
+#[test]
+fn text_from_the_other_end_is_cleaned() {
+    assert_eq!(
+        chat::clean("hi\x1b]0;owned\x07 there\n"),
+        "hi]0;owned there"
+    );
+    assert_eq!(
+        chat::clean(&"y".repeat(CHAT_MAX_LEN + 5)).chars().count(),
+        CHAT_MAX_LEN
+    );
+}
@@ -0,0 +1,29 @@
+// The debug overlay's view of the game
+
+use kaazap::{
+    debug::DebugOverlay,
+    game::{GameAction, GameState},
+    player::Player,
+};
+
+fn player_flags(state: &GameState) -> String {
+    DebugOverlay::new()
+        .lines("game", Some(state))
+        .into_iter()
+        .find(|line| line.starts_with("Player "))
+        .unwrap()
+}
+
+#[test]
+fn played_card_is_flagged_until_the_turn_ends() {
+    let mut state = GameState::new();
+    assert_eq!(player_flags(&state), "Player (human): -");
+
+    assert!(state.apply_seat_action(Player::Player, GameAction::PlayHand { index: 0 }));
+    assert!(state.seats[Player::Player].played_card);
+    assert_eq!(player_flags(&state), "Player (human): played");
+
+    assert!(state.apply_seat_action(Player::Player, GameAction::Stand));
+    assert!(!state.seats[Player::Player].played_card);
+    assert_eq!(player_flags(&state), "Player (human): stood");
+}
@@ -0,0 +1,783 @@
+// Networked games, played between a host and a client over loopback
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::{Duration, Instant},
+};
+
+use kaazap::{
+    CHAT_MAX_LEN, ROUNDS_TO_WIN, TARGET_SCORE,
+    card::LogicCard,
+    game::GamePhase,
+    net::NetSession,
+    player::Player,
+    protocol::{Message, PROTOCOL_VERSION},
+    shuffle::Seed,
+};
+
+fn host() -> NetSession {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    NetSession::host(listener, "Alice").unwrap()
+}
+
+fn join(host: &NetSession, name: &str) -> NetSession {
+    let stream = TcpStream::connect(("127.0.0.1", host.port().unwrap())).unwrap();
+    NetSession::join(stream, name).unwrap()
+}
+
+// Update both ends, like their game loops would, until `done` or a couple of seconds pass
+fn pump(
+    host: &mut NetSession,
+    client: &mut NetSession,
+    done: impl Fn(&NetSession, &NetSession) -> bool,
+) {
+    for _ in 0..200 {
+        host.update();
+        client.update();
+        if done(host, client) {
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("timed out");
+}
+
+fn connected() -> (NetSession, NetSession) {
+    let mut host = host();
+    let mut client = join(&host, "Bob");
+    pump(&mut host, &mut client, |host, client| {
+        host.is_shuffled() && client.is_shuffled()
+    });
+    (host, client)
+}
+
+fn watch(host: &NetSession) -> NetSession {
+    let stream = TcpStream::connect(("127.0.0.1", host.port().unwrap())).unwrap();
+    NetSession::watch(stream, "Carol").unwrap()
+}
+
+// Like pump, with someone watching as well
+fn pump_watched(
+    host: &mut NetSession,
+    client: &mut NetSession,
+    spectator: &mut NetSession,
+    done: impl Fn(&NetSession) -> bool,
+) {
+    for _ in 0..200 {
+        host.update();
+        client.update();
+        spectator.update();
+        if done(spectator) {
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("timed out");
+}
+
+// Run the game in text mode, with its output read line by line on another thread
+fn spawn_plain(args: &[&str]) -> (Child, ChildStdin, Receiver<String>) {
+    let mut game = Command::new(env!("CARGO_BIN_EXE_kaazap"))
+        .arg("--plain")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdin = game.stdin.take().unwrap();
+    let stdout = BufReader::new(game.stdout.take().unwrap());
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in stdout.lines().map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                return;
+            }
+        }
+    });
+
+    (game, stdin, rx)
+}
+
+// The first line printed that starts with `start`, waiting a few seconds at most
+fn wait_for_line(output: &Receiver<String>, start: &str) -> String {
+    loop {
+        match output.recv_timeout(Duration::from_secs(5)) {
+            Ok(line) if line.starts_with(start) => return line,
+            Ok(_) => {}
+            Err(_) => panic!("never printed {start:?}"),
+        }
+    }
+}
+
+fn values(cards: &[LogicCard]) -> Vec<i32> {
+    cards.iter().map(|card| card.value).collect()
+}
+
+// The client sees the host's seat as its opponent, and the other way round
+fn assert_same_table(host: &NetSession, client: &NetSession) {
+    for seat in Player::ALL {
+        let (ours, theirs) = (
+            &host.game_state.seats[seat],
+            &client.game_state.seats[seat.other()],
+        );
+        assert_eq!(values(&ours.dealer_row), values(&theirs.dealer_row));
+        assert_eq!(values(&ours.played_row), values(&theirs.played_row));
+        assert_eq!(ours.rounds_won, theirs.rounds_won);
+        assert_eq!(ours.stood, theirs.stood);
+    }
+}
+
+#[test]
+fn client_sees_the_hosts_moves_and_its_own() {
+    let (mut host, mut client) = connected();
+    assert_eq!(client.peer_name.as_deref(), Some("Alice"));
+    assert_eq!(host.game_state.seats[Player::Opponent].name, "Bob");
+
+    // Host draws, then the client does
+    host.handle_game_input('d');
+    pump(&mut host, &mut client, |_, client| {
+        !client.game_state.seats[Player::Opponent]
+            .dealer_row
+            .is_empty()
+    });
+    // Only applied at the client once the host sends it back
+    client.handle_game_input('d');
+    pump(&mut host, &mut client, |_, client| {
+        !client.game_state.seats[Player::Player]
+            .dealer_row
+            .is_empty()
+    });
+
+    assert_same_table(&host, &client);
+    assert_eq!(client.game_state.seats[Player::Player].dealer_row.len(), 1);
+    assert!(matches!(
+        client.game_state.game_phase,
+        GamePhase::Turn {
+            seat: Player::Opponent
+        }
+    ));
+}
+
+#[test]
+fn host_side_cards_stay_hidden_until_played() {
+    let (mut host, mut client) = connected();
+
+    let hidden = |client: &NetSession| {
+        values(
+            &client.game_state.seats[Player::Opponent]
+                .hand
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>(),
+        )
+    };
+    assert_eq!(hidden(&client), [0, 0, 0, 0]);
+    // The client's own hand is dealt by the host
+    assert_eq!(
+        client.game_state.seats[Player::Player]
+            .hand
+            .iter()
+            .flatten()
+            .count(),
+        4
+    );
+
+    host.handle_game_input('2');
+    pump(&mut host, &mut client, |_, client| {
+        !client.game_state.seats[Player::Opponent]
+            .played_row
+            .is_empty()
+    });
+
+    assert_eq!(
+        values(&client.game_state.seats[Player::Opponent].played_row),
+        [3]
+    );
+    assert_eq!(hidden(&client), [0, 0, 0]);
+}
+
+#[test]
+fn plus_minus_cards_are_flipped_at_both_ends() {
+    let (mut host, mut client) = connected();
+    assert_eq!(
+        client.game_state.seats[Player::Player].hand[2],
+        Some(LogicCard::plus_minus(1))
+    );
+
+    // Shift and the card's number plays it as minus
+    host.handle_game_input('$');
+    pump(&mut host, &mut client, |_, client| {
+        !client.game_state.seats[Player::Opponent]
+            .played_row
+            .is_empty()
+    });
+    host.handle_game_input('d');
+    pump(&mut host, &mut client, |_, client| {
+        client.game_state.active_seat() == Some(Player::Player)
+    });
+    client.handle_game_input('#');
+    pump(&mut host, &mut client, |_, client| {
+        !client.game_state.seats[Player::Player]
+            .played_row
+            .is_empty()
+    });
+
+    assert_same_table(&host, &client);
+    assert_eq!(
+        values(&client.game_state.seats[Player::Opponent].played_row),
+        [-2]
+    );
+    assert_eq!(
+        values(&host.game_state.seats[Player::Opponent].played_row),
+        [-1]
+    );
+}
+
+#[test]
+fn moves_out_of_turn_are_ignored() {
+    let (mut host, mut client) = connected();
+
+    // It's the host's turn, so the client's draw goes nowhere
+    client.handle_game_input('d');
+    pump(&mut host, &mut client, |_, _| true);
+    thread::sleep(Duration::from_millis(50));
+    pump(&mut host, &mut client, |_, _| true);
+
+    assert!(
+        host.game_state.seats[Player::Opponent]
+            .dealer_row
+            .is_empty()
+    );
+    assert!(
+        client.game_state.seats[Player::Player]
+            .dealer_row
+            .is_empty()
+    );
+    assert!(client.is_connected());
+}
+
+#[test]
+fn client_rejoins_where_it_left_off() {
+    let (mut host, mut client) = connected();
+
+    host.handle_game_input('d');
+    pump(&mut host, &mut client, |_, client| {
+        !client.game_state.seats[Player::Opponent]
+            .dealer_row
+            .is_empty()
+    });
+    client.handle_game_input('d');
+    pump(&mut host, &mut client, |_, client| {
+        !client.game_state.seats[Player::Player]
+            .dealer_row
+            .is_empty()
+    });
+
+    let token = client.rejoin_token().map(str::to_string);
+    drop(client);
+    let stream = TcpStream::connect(("127.0.0.1", host.port().unwrap())).unwrap();
+    let mut client = NetSession::rejoin(stream, "Bob", token).unwrap();
+    pump(&mut host, &mut client, |host, client| {
+        host.is_connected() && client.is_connected()
+    });
+
+    assert_same_table(&host, &client);
+    // And play carries on
+    host.handle_game_input('s');
+    pump(&mut host, &mut client, |_, client| {
+        client.game_state.seats[Player::Opponent].stood
+    });
+    assert!(client.is_connected());
+}
+
+#[test]
+fn seat_is_not_given_to_someone_else_using_the_same_name() {
+    let (mut host, client) = connected();
+    let side_cards = |session: &NetSession| {
+        let hand = &session.game_state.seats[Player::Player].hand;
+        values(&hand.iter().flatten().copied().collect::<Vec<_>>())
+    };
+    let hand = side_cards(&client);
+    assert!(client.rejoin_token().is_some());
+
+    drop(client);
+    let mut impostor = join(&host, "Bob");
+    pump(&mut host, &mut impostor, |_, impostor| {
+        impostor.closed_reason().is_some()
+    });
+
+    assert_eq!(
+        impostor.closed_reason(),
+        Some("Alice turned you away, someone else may be playing already.")
+    );
+    // Never sent the rules, so never saw Bob's side cards
+    assert_ne!(side_cards(&impostor), hand);
+    assert!(!impostor.is_shuffled());
+    assert!(!host.is_connected());
+}
+
+#[test]
+fn host_wins_if_the_client_does_not_come_back() {
+    let (mut host, client) = connected();
+    host.reconnect_window = Duration::from_millis(50);
+
+    drop(client);
+    for _ in 0..100 {
+        host.update();
+        if host.closed_reason().is_some() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert_eq!(
+        host.closed_reason(),
+        Some("Bob didn't come back, so you win.")
+    );
+    assert!(matches!(
+        host.game_state.game_phase,
+        GamePhase::GameOver {
+            winner: Player::Player
+        }
+    ));
+}
+
+#[test]
+fn resigning_ends_the_match_at_both_ends() {
+    let (mut host, mut client) = connected();
+
+    client.handle_game_input('r');
+    pump(&mut host, &mut client, |host, client| {
+        matches!(host.game_state.game_phase, GamePhase::GameOver { .. })
+            && matches!(client.game_state.game_phase, GamePhase::GameOver { .. })
+    });
+
+    assert!(matches!(
+        host.game_state.game_phase,
+        GamePhase::GameOver {
+            winner: Player::Player
+        }
+    ));
+    assert!(matches!(
+        client.game_state.game_phase,
+        GamePhase::GameOver {
+            winner: Player::Opponent
+        }
+    ));
+}
+
+#[test]
+fn host_hangs_up_on_a_different_protocol_version() {
+    let mut host = host();
+    let mut stream = TcpStream::connect(("127.0.0.1", host.port().unwrap())).unwrap();
+    let hello = Message::Hello {
+        version: PROTOCOL_VERSION + 1,
+        name: "Future".to_string(),
+        spectator: false,
+        token: None,
+    };
+    stream.write_all(hello.to_line().as_bytes()).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(2)))
+        .unwrap();
+
+    for _ in 0..50 {
+        host.update();
+        thread::sleep(Duration::from_millis(10));
+    }
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+
+    // The host says who it is, so the client can explain, then hangs up
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(
+        Message::from_line(&line).unwrap(),
+        Message::Hello {
+            version: PROTOCOL_VERSION,
+            name: "Alice".to_string(),
+            spectator: false,
+            token: None,
+        }
+    );
+    line.clear();
+    assert_eq!(reader.read_line(&mut line).unwrap(), 0);
+    assert!(!host.is_connected());
+}
+
+#[test]
+fn client_checks_the_deal_when_the_match_ends() {
+    let (mut host, mut client) = connected();
+
+    host.handle_game_input('d');
+    pump(&mut host, &mut client, |_, client| {
+        !client.game_state.seats[Player::Opponent]
+            .dealer_row
+            .is_empty()
+    });
+    client.handle_game_input('d');
+    pump(&mut host, &mut client, |_, client| {
+        !client.game_state.seats[Player::Player]
+            .dealer_row
+            .is_empty()
+    });
+
+    host.handle_game_input('r');
+    pump(&mut host, &mut client, |_, client| !client.chat.is_empty());
+
+    assert_eq!(
+        client.chat,
+        ["* Fair deal: all 2 dealer cards came from the agreed shuffle"]
+    );
+    assert!(client.is_connected());
+}
+
+#[test]
+fn client_refuses_a_shuffle_changed_after_it_answered() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let mut client = NetSession::join(stream, "Bob").unwrap();
+
+    // A host that commits to one secret, then swaps it once it has the client's seed
+    let (mut host, _) = listener.accept().unwrap();
+    let lines = BufReader::new(host.try_clone().unwrap()).lines();
+    let first = Seed::random();
+    let opening = [
+        Message::Hello {
+            version: PROTOCOL_VERSION,
+            name: "Mallory".to_string(),
+            spectator: false,
+            token: Some("seat".to_string()),
+        },
+        Message::Rules {
+            target_score: TARGET_SCORE,
+            rounds_to_win: ROUNDS_TO_WIN,
+            hand: vec![LogicCard::new(1); 4],
+            opponent_hand: 4,
+        },
+        Message::Commit {
+            commitment: first.commitment(),
+            seed: None,
+        },
+    ];
+    for message in opening {
+        host.write_all(message.to_line().as_bytes()).unwrap();
+    }
+
+    let seed = thread::spawn(move || {
+        lines
+            .map_while(Result::ok)
+            .find_map(|line| match Message::from_line(&line) {
+                Ok(Message::Seed { seed }) => Some(seed),
+                _ => None,
+            })
+            .unwrap()
+    });
+    while !seed.is_finished() {
+        client.update();
+        thread::sleep(Duration::from_millis(10));
+    }
+    let swapped = Message::Commit {
+        commitment: Seed::random().commitment(),
+        seed: Some(seed.join().unwrap()),
+    };
+    host.write_all(swapped.to_line().as_bytes()).unwrap();
+
+    for _ in 0..200 {
+        client.update();
+        if client.closed_reason().is_some() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(
+        client.closed_reason(),
+        Some("The host changed the shuffle after seeing our part of it.")
+    );
+    assert!(!client.is_shuffled());
+}
+
+#[test]
+fn next_match_is_dealt_from_a_new_shuffle() {
+    let (mut host, mut client) = connected();
+
+    host.handle_game_input('r');
+    pump(&mut host, &mut client, |_, client| !client.is_shuffled());
+    client.handle_game_input('g');
+    pump(&mut host, &mut client, |host, client| {
+        host.is_shuffled() && client.is_shuffled()
+    });
+
+    host.handle_game_input('d');
+    pump(&mut host, &mut client, |_, client| {
+        !client.game_state.seats[Player::Opponent]
+            .dealer_row
+            .is_empty()
+    });
+    assert_same_table(&host, &client);
+}
+
+#[test]
+fn spectator_joins_mid_match_and_follows_along() {
+    let (mut host, mut client) = connected();
+    host.handle_game_input('d');
+    pump(&mut host, &mut client, |_, client| {
+        !client.game_state.seats[Player::Opponent]
+            .dealer_row
+            .is_empty()
+    });
+
+    let mut spectator = watch(&host);
+    pump_watched(&mut host, &mut client, &mut spectator, |spectator| {
+        spectator.is_connected()
+    });
+    assert_eq!(host.spectator_count(), 1);
+    // Seats the host's way round, with the snapshot's hands still hidden
+    let table = &spectator.game_state.seats;
+    assert_eq!(table[Player::Player].name, "Alice");
+    assert_eq!(table[Player::Opponent].name, "Bob");
+    assert_eq!(
+        values(&table[Player::Player].dealer_row),
+        values(&host.game_state.seats[Player::Player].dealer_row)
+    );
+    assert!(!spectator.game_state.shows_hand(Player::Opponent));
+
+    // Then live moves, from either seat
+    client.handle_game_input('s');
+    pump_watched(&mut host, &mut client, &mut spectator, |spectator| {
+        spectator.game_state.seats[Player::Opponent].stood
+    });
+    host.handle_game_input('2');
+    pump_watched(&mut host, &mut client, &mut spectator, |spectator| {
+        !spectator.game_state.seats[Player::Player]
+            .played_row
+            .is_empty()
+    });
+
+    assert_eq!(
+        values(&spectator.game_state.seats[Player::Player].played_row),
+        [3]
+    );
+    assert_eq!(
+        spectator.overlay_text().unwrap()[1..],
+        ["Bob stood", "Alice played 3"]
+    );
+    assert!(spectator.is_connected());
+}
+
+#[test]
+fn spectators_see_both_hands_once_the_delay_is_up() {
+    let mut host = host();
+    host.spectator_hands = Some(Duration::ZERO);
+    let mut client = join(&host, "Bob");
+    pump(&mut host, &mut client, |host, client| {
+        host.is_shuffled() && client.is_shuffled()
+    });
+
+    let mut spectator = watch(&host);
+    pump_watched(&mut host, &mut client, &mut spectator, |spectator| {
+        spectator.game_state.open_hands
+    });
+
+    for seat in Player::ALL {
+        assert!(spectator.game_state.shows_hand(seat));
+        let hand = |session: &NetSession| {
+            values(
+                &session.game_state.seats[seat]
+                    .hand
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .collect::<Vec<_>>(),
+            )
+        };
+        assert_eq!(hand(&spectator), hand(&host));
+    }
+}
+
+#[test]
+fn spectators_see_hands_only_as_they_were_the_delay_ago() {
+    let delay = Duration::from_millis(300);
+    let mut host = host();
+    host.spectator_hands = Some(delay);
+    let mut client = join(&host, "Bob");
+    pump(&mut host, &mut client, |host, client| {
+        host.is_shuffled() && client.is_shuffled()
+    });
+
+    // Every Hands message the spectator gets, with when it arrived
+    let mut stream = TcpStream::connect(("127.0.0.1", host.port().unwrap())).unwrap();
+    let hello = Message::Hello {
+        version: PROTOCOL_VERSION,
+        name: "Carol".to_string(),
+        spectator: true,
+        token: None,
+    };
+    stream.write_all(hello.to_line().as_bytes()).unwrap();
+    let (tx, hands) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            if let Ok(Message::Hands { player, .. }) = Message::from_line(&line) {
+                let _ = tx.send((Instant::now(), player));
+            }
+        }
+    });
+    pump(&mut host, &mut client, |host, _| {
+        host.spectator_count() == 1
+    });
+
+    let played = Instant::now();
+    host.handle_game_input('2');
+    let shown = loop {
+        assert!(played.elapsed() < Duration::from_secs(2), "never shown");
+        host.update();
+        client.update();
+        match hands.try_recv() {
+            Ok((at, player)) if player[1].is_none() => break at,
+            _ => thread::sleep(Duration::from_millis(10)),
+        }
+    };
+    assert!(shown - played >= delay);
+}
+
+#[test]
+fn spectators_cannot_move_or_take_a_seat() {
+    let (mut host, mut client) = connected();
+    let mut spectator = watch(&host);
+    pump_watched(&mut host, &mut client, &mut spectator, |spectator| {
+        spectator.is_connected()
+    });
+
+    spectator.handle_game_input('d');
+    spectator.handle_game_input('r');
+    // Joining as a player while both seats are taken gets turned away
+    let mut late = join(&host, "Dave");
+    pump(&mut host, &mut late, |_, late| {
+        late.closed_reason().is_some()
+    });
+    pump_watched(&mut host, &mut client, &mut spectator, |_| true);
+
+    assert!(host.game_state.seats[Player::Player].dealer_row.is_empty());
+    assert!(!matches!(
+        host.game_state.game_phase,
+        GamePhase::GameOver { .. }
+    ));
+    assert_eq!(
+        late.closed_reason(),
+        Some("Alice turned you away, someone else may be playing already.")
+    );
+    assert_eq!(host.peer_name.as_deref(), Some("Bob"));
+}
+
+#[test]
+fn spectator_that_stops_reading_cannot_hold_up_the_host() {
+    let mut host = host();
+    let mut stream = TcpStream::connect(("127.0.0.1", host.port().unwrap())).unwrap();
+    let hello = Message::Hello {
+        version: PROTOCOL_VERSION,
+        name: "Idle".to_string(),
+        spectator: true,
+        token: None,
+    };
+    stream.write_all(hello.to_line().as_bytes()).unwrap();
+    for _ in 0..200 {
+        host.update();
+        if host.spectator_count() == 1 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(host.spectator_count(), 1);
+
+    // The spectator never reads, so its socket fills up and sends start to queue
+    let started = Instant::now();
+    let text = "x".repeat(CHAT_MAX_LEN);
+    while host.spectator_count() > 0 {
+        assert!(started.elapsed() < Duration::from_secs(20), "never dropped");
+        host.send_chat(&text);
+        host.update();
+    }
+    drop(stream);
+}
+
+#[test]
+fn chat_reaches_the_other_player_and_spectators() {
+    let (mut host, mut client) = connected();
+    let mut spectator = watch(&host);
+    pump_watched(&mut host, &mut client, &mut spectator, |spectator| {
+        spectator.is_connected()
+    });
+
+    client.send_chat("Nice hand");
+    pump(&mut host, &mut client, |host, _| host.chat.len() == 2);
+    host.send_chat("Thanks");
+    pump_watched(&mut host, &mut client, &mut spectator, |spectator| {
+        spectator.chat.len() == 2
+    });
+
+    assert_eq!(
+        host.chat,
+        ["* Carol is watching", "Bob: Nice hand", "Alice: Thanks"]
+    );
+    assert_eq!(client.chat, ["Bob: Nice hand", "Alice: Thanks"]);
+}
+
+#[test]
+fn chat_is_cleaned_and_said_by_whoever_sent_it() {
+    let mut host = host();
+    let mut stream = TcpStream::connect(("127.0.0.1", host.port().unwrap())).unwrap();
+    let messages = [
+        Message::Hello {
+            version: PROTOCOL_VERSION,
+            name: "Mallory".to_string(),
+            spectator: false,
+            token: None,
+        },
+        Message::Chat {
+            from: "Alice".to_string(),
+            text: format!("\x1b[2J{}", "x".repeat(CHAT_MAX_LEN * 2)),
+        },
+    ];
+    for message in messages {
+        stream.write_all(message.to_line().as_bytes()).unwrap();
+    }
+
+    for _ in 0..200 {
+        host.update();
+        if !host.chat.is_empty() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    let said = format!("[2J{}", "x".repeat(CHAT_MAX_LEN - 3));
+    assert_eq!(host.chat, [format!("Mallory: {said}")]);
+}
+
+#[test]
+fn two_processes_play_over_loopback() {
+    let (mut host, mut host_in, host_out) = spawn_plain(&["--host", "0", "--name", "Ada"]);
+    let waiting = wait_for_line(&host_out, "Waiting for someone to join on port ");
+    let port = waiting
+        .trim_start_matches("Waiting for someone to join on port ")
+        .trim_end_matches('.');
+    let address = format!("127.0.0.1:{port}");
+    let (mut guest, mut guest_in, guest_out) = spawn_plain(&["--join", &address, "--name", "Bea"]);
+
+    wait_for_line(&host_out, "Ada to move");
+    host_in.write_all(b"stand\n").unwrap();
+    wait_for_line(&guest_out, "Ada stood");
+    wait_for_line(&guest_out, "Bea to move");
+    guest_in.write_all(b"stand\n").unwrap();
+    wait_for_line(&host_out, "Bea stood");
+
+    // The end of either's input ends its game
+    drop(host_in);
+    drop(guest_in);
+    assert!(host.wait().unwrap().success());
+    assert!(guest.wait().unwrap().success());
+}
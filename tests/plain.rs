@@ -0,0 +1,145 @@
+// Text-mode play, driven by lines of commands as a script or screen reader user would type them
+
+use std::{
+    io::{Cursor, Write},
+    process::{Command as Process, Stdio},
+};
+
+use kaazap::{
+    deck::ScriptedDeck,
+    game::{GameAction, GameState},
+    plain::{self, Command, Sign},
+    player::Player,
+};
+
+// Play a game dealt from `deck`, with the opponent making `opponent_moves`, returning all output
+fn play(deck: &[i32], opponent_moves: Vec<GameAction>, input: &str) -> Vec<String> {
+    let mut state = GameState::with_script(Box::new(ScriptedDeck::new(deck)), opponent_moves);
+    state.seats[Player::Player].name = "You".to_string();
+
+    let mut output = vec![];
+    plain::run(state, Cursor::new(input), &mut output).unwrap();
+    String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+fn has_line(output: &[String], line: &str) {
+    assert!(
+        output.iter().any(|printed| printed == line),
+        "no line {line:?} in:\n{}",
+        output.join("\n")
+    );
+}
+
+#[test]
+fn commands_are_parsed() {
+    assert_eq!(plain::parse_command("hit"), Ok(Command::Hit));
+    assert_eq!(plain::parse_command("  stand "), Ok(Command::Stand));
+    assert_eq!(
+        plain::parse_command("play 2"),
+        Ok(Command::Play {
+            index: 1,
+            sign: None
+        })
+    );
+    assert_eq!(
+        plain::parse_command("play 3 -"),
+        Ok(Command::Play {
+            index: 2,
+            sign: Some(Sign::Minus)
+        })
+    );
+    assert_eq!(plain::parse_command("next"), Ok(Command::Next));
+
+    assert!(plain::parse_command("play 0").is_err());
+    assert!(plain::parse_command("play 2 x").is_err());
+    assert!(plain::parse_command("fold").is_err());
+    assert!(plain::parse_command("").is_err());
+}
+
+#[test]
+fn table_is_printed_after_each_move() {
+    let output = play(
+        &[10],
+        vec![GameAction::Stand],
+        "hit\nplay 3 -\nplay 3\nstand\n",
+    );
+
+    has_line(
+        &output,
+        "You: 0 nothing dealt, side cards 1) +5 2) +3 3) +6 4) ±2",
+    );
+    has_line(&output, "Opponent: 0 nothing dealt, 4 side cards");
+    has_line(&output, "You to move: hit, stand or play N");
+    has_line(&output, "You drew 10");
+    has_line(&output, "Opponent stood");
+    has_line(&output, "Side card 3 is +6 and can't be flipped");
+    has_line(&output, "You played 6");
+    has_line(&output, "You won the round");
+    has_line(
+        &output,
+        "You: 16 from 10 and played +6, side cards 1) +5 2) +3 3) used 4) ±2, standing",
+    );
+    has_line(&output, "Rounds won: You 1, Opponent 0");
+    has_line(&output, "Round over, type next to play on");
+}
+
+#[test]
+fn plus_minus_card_counts_the_way_it_is_played() {
+    let output = play(
+        &[10, 9],
+        vec![GameAction::Stand],
+        "hit\nhit\nplay 4 -\nstand\n",
+    );
+
+    has_line(&output, "You played -2");
+    has_line(
+        &output,
+        "You: 17 from 10 9 and played -2, side cards 1) +5 2) +3 3) +6 4) used, standing",
+    );
+}
+
+#[test]
+fn moves_out_of_turn_are_explained() {
+    let output = play(&[], vec![GameAction::Stand], "next\nstand\nhit\nnext\n");
+
+    has_line(&output, "That move isn't allowed now");
+    has_line(&output, "It's not your move, type next to play on");
+    has_line(&output, "New round");
+}
+
+#[test]
+fn quit_stops_reading() {
+    let output = play(&[10], vec![], "help\nquit\nhit\n");
+
+    let helps = output
+        .iter()
+        .filter(|line| line.starts_with("Commands:"))
+        .collect::<Vec<_>>();
+    assert_eq!(helps.len(), 2);
+    assert!(helps[0].contains("a ± card counting that way"));
+    assert!(!output.iter().any(|line| line == "You drew 10"));
+}
+
+#[test]
+fn binary_plays_from_stdin() {
+    let mut game = Process::new(env!("CARGO_BIN_EXE_kaazap"))
+        .args(["--plain", "--name", "Ada"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    game.stdin
+        .take()
+        .unwrap()
+        .write_all(b"stand\nquit\n")
+        .unwrap();
+
+    let finished = game.wait_with_output().unwrap();
+    assert!(finished.status.success());
+    let output = String::from_utf8(finished.stdout).unwrap();
+    assert!(output.lines().any(|line| line == "Ada stood"), "{output}");
+}
@@ -0,0 +1,124 @@
+// Terminal rendering into a byte buffer: which cells are written, in how many runs,
+// and that the stats match what was actually written
+
+use crossterm::style::Color;
+use kaazap::{
+    config::Config,
+    frame::{Cell, Frame, Style, new_frame},
+    render::{RenderStats, RenderTarget, Terminal},
+};
+
+// Switching from no style to the default one, and back at the end of a frame
+const DEFAULT_STYLE: &str = "\x1b[0m\x1b[39m\x1b[49m";
+const RESET: &str = "\x1b[0m";
+
+fn blank(cols: usize, rows: usize) -> Frame {
+    new_frame(&Config::from_size(cols as u16, rows as u16))
+}
+
+fn put(frame: &mut Frame, x: usize, y: usize, text: &str, style: Style) {
+    for (i, ch) in text.chars().enumerate() {
+        frame[x + i][y] = Cell::new(ch, style);
+    }
+}
+
+// Render `curr` over `last` into a fresh buffer
+fn render(last: &Frame, curr: &Frame) -> (RenderStats, String) {
+    let mut out = vec![];
+    let stats = Terminal::new(&mut out).render(last, curr, false).unwrap();
+    (stats, String::from_utf8(out).unwrap())
+}
+
+// Where the cursor is moved to, 1-based "row;col" as in the escape code
+fn moves(output: &str) -> Vec<String> {
+    output
+        .split("\x1b[")
+        .filter_map(|code| code.split_once('H').map(|(at, _)| at.to_string()))
+        .collect()
+}
+
+#[test]
+fn unchanged_frame_writes_nothing() {
+    let mut frame = blank(10, 2);
+    put(&mut frame, 0, 0, "hello", Style::new());
+
+    let (stats, output) = render(&frame, &frame);
+
+    assert_eq!(stats, RenderStats::default());
+    assert_eq!(output, "");
+}
+
+#[test]
+fn neighbouring_changes_are_one_run() {
+    let last = blank(10, 2);
+    let mut curr = last.clone();
+    put(&mut curr, 2, 0, "abc", Style::new());
+    put(&mut curr, 7, 0, "z", Style::new());
+
+    let (stats, output) = render(&last, &curr);
+
+    assert_eq!(
+        output,
+        format!("\x1b[1;3H{DEFAULT_STYLE}abc\x1b[1;8Hz{RESET}")
+    );
+    assert_eq!(stats.cells_changed, 4);
+    assert_eq!(stats.bytes_written, output.len());
+}
+
+#[test]
+fn style_changes_dont_break_a_run() {
+    let last = blank(10, 2);
+    let mut curr = last.clone();
+    put(&mut curr, 0, 1, "ab", Style::new());
+    put(&mut curr, 2, 1, "cd", Style::new().fg(Color::Red));
+
+    let (stats, output) = render(&last, &curr);
+
+    assert_eq!(moves(&output), ["2;1"]);
+    assert!(output.contains("ab\x1b[38;5;9mcd"), "{output:?}");
+    assert_eq!(stats.cells_changed, 4);
+}
+
+#[test]
+fn runs_stop_at_the_end_of_a_row() {
+    let last = blank(4, 2);
+    let mut curr = last.clone();
+    put(&mut curr, 3, 0, "x", Style::new());
+    put(&mut curr, 0, 1, "y", Style::new());
+
+    let (_, output) = render(&last, &curr);
+
+    assert_eq!(moves(&output), ["1;4", "2;1"]);
+}
+
+#[test]
+fn wide_characters_are_printed_once() {
+    let last = blank(6, 1);
+    let mut curr = last.clone();
+    curr[0][0] = Cell::new('日', Style::new());
+    curr[1][0] = Cell::continuation(Style::new());
+    put(&mut curr, 2, 0, "x", Style::new());
+
+    let (stats, output) = render(&last, &curr);
+
+    assert_eq!(output, format!("\x1b[1;1H{DEFAULT_STYLE}日x{RESET}"));
+    // the continuation still counts as a changed cell
+    assert_eq!(stats.cells_changed, 3);
+}
+
+#[test]
+fn bytes_written_counts_each_frame() {
+    let first = blank(10, 3);
+    let mut second = first.clone();
+    put(&mut second, 1, 1, "moved", Style::new().bold());
+
+    let mut out = vec![];
+    let mut terminal = Terminal::new(&mut out);
+    let forced = terminal.render(&first, &first, true).unwrap();
+    let update = terminal.render(&first, &second, false).unwrap();
+    drop(terminal);
+
+    assert_eq!(forced.cells_changed, 30);
+    assert_eq!(update.cells_changed, 5);
+    assert_eq!(forced.bytes_written + update.bytes_written, out.len());
+}
@@ -0,0 +1,81 @@
+// Commit-reveal shuffles, checked without a network
+
+use kaazap::{
+    deck::DeckSource,
+    shuffle::{self, Seed},
+};
+
+fn deal(host: &Seed, client: &Seed, count: usize) -> Vec<i32> {
+    let mut deck = shuffle::deck(host, client);
+    (0..count).map(|_| deck.deal().value).collect()
+}
+
+#[test]
+fn honest_deal_verifies() {
+    let (host, client) = (Seed::random(), Seed::random());
+    let dealt = deal(&host, &client, 12);
+
+    assert_eq!(
+        shuffle::verify_deal(&host.commitment(), &host, &client, &dealt),
+        Ok(())
+    );
+}
+
+#[test]
+fn a_swapped_card_is_caught() {
+    let (host, client) = (Seed::random(), Seed::random());
+    let mut dealt = deal(&host, &client, 6);
+    dealt[3] = (dealt[3] + 1) % 11;
+
+    let err = shuffle::verify_deal(&host.commitment(), &host, &client, &dealt).unwrap_err();
+    assert!(err.starts_with("dealer card 4 was"), "{err}");
+}
+
+#[test]
+fn a_seed_other_than_the_committed_one_is_caught() {
+    let (committed, revealed, client) = (Seed::random(), Seed::random(), Seed::random());
+    let dealt = deal(&revealed, &client, 6);
+
+    assert!(shuffle::verify_deal(&committed.commitment(), &revealed, &client, &dealt).is_err());
+}
+
+#[test]
+fn both_seeds_change_the_deal() {
+    let (host, client) = (Seed::random(), Seed::random());
+
+    assert_ne!(
+        shuffle::deck_seed(&host, &client),
+        shuffle::deck_seed(&host, &Seed::random())
+    );
+    assert_ne!(
+        shuffle::deck_seed(&host, &client),
+        shuffle::deck_seed(&Seed::random(), &client)
+    );
+}
+
+#[test]
+fn seeds_travel_as_hex() {
+    let seed = Seed::random();
+    let json = serde_json::to_string(&seed).unwrap();
+
+    assert_eq!(json.len(), 64 + 2);
+    assert_eq!(serde_json::from_str::<Seed>(&json).unwrap(), seed);
+    assert!(serde_json::from_str::<Seed>("\"not hex\"").is_err());
+}
+
+// Worked out from the ChaCha20 spec, independently of rand, so a build that deals anything
+// else would have an honest host's deal fail to verify
+#[test]
+fn same_seeds_deal_the_same_cards_on_every_build() {
+    let host = Seed::try_from("11".repeat(32)).unwrap();
+    let client = Seed::try_from("22".repeat(32)).unwrap();
+
+    assert_eq!(
+        shuffle::to_hex(&shuffle::deck_seed(&host, &client)),
+        "5189c77d29fe5d546a045ec46986852785fea5c13ac7da9c115ff5fb6edf817c"
+    );
+    assert_eq!(
+        deal(&host, &client, 16),
+        [5, 6, 10, 9, 8, 4, 3, 7, 1, 9, 10, 0, 4, 2, 9, 10]
+    );
+}
@@ -0,0 +1,271 @@
+// Golden snapshot tests: screens are rendered headlessly at fixed terminal sizes
+// and compared against the text in tests/golden.
+//
+// After an intended layout change, regenerate the golden files with
+//     UPDATE_GOLDEN=1 cargo test --test snapshots
+// and review the diff.
+
+use std::{env, fs, path::PathBuf};
+
+use crossterm::event::KeyCode;
+use kaazap::{
+    app::App,
+    board::BoardView,
+    card::{CardStyle, LogicCard},
+    config::Config,
+    frame::{Frame, new_frame},
+    game::{GamePhase, GameState, RoundOutcome},
+    player::Player,
+    render::{RenderTarget, TextTarget},
+    settings::{Opponent, Settings},
+    theme::Theme,
+};
+
+fn settings() -> Settings {
+    Settings {
+        theme: Theme::Monochrome,
+        card_style: CardStyle::Ascii,
+        animations: false,
+        opponent: Opponent::Computer,
+    }
+}
+
+fn cards(values: &[i32]) -> Vec<LogicCard> {
+    values.iter().map(|&value| LogicCard::new(value)).collect()
+}
+
+fn hand(values: &[Option<i32>]) -> Vec<Option<LogicCard>> {
+    values
+        .iter()
+        .map(|value| value.map(LogicCard::new))
+        .collect()
+}
+
+fn render_text(frame: &Frame) -> String {
+    let mut target = TextTarget::new();
+    target.render(frame, frame, true).unwrap();
+    target.text().to_string()
+}
+
+fn draw_board(state: &GameState, config: &Config) -> String {
+    let settings = settings();
+    let mut board_view = BoardView::new(config.clone());
+    board_view.theme = settings.theme;
+    board_view.card_style = settings.card_style;
+
+    let mut frame = new_frame(config);
+    board_view.draw(state, &mut frame);
+    render_text(&frame)
+}
+
+fn assert_golden(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.txt"));
+
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing golden file {}", path.display()));
+    assert!(
+        expected == actual,
+        "{name} doesn't match {}\n--- expected\n{expected}\n--- actual\n{actual}",
+        path.display()
+    );
+}
+
+// Player on 15 with a side card played, opponent waiting on 13
+fn mid_round() -> GameState {
+    let mut state = GameState::new();
+    state.seats[Player::Player].dealer_row = cards(&[7, 5]);
+    state.seats[Player::Player].played_row = cards(&[3]);
+    state.seats[Player::Player].hand = hand(&[Some(5), None, Some(6), Some(2)]);
+    state.seats[Player::Opponent].dealer_row = cards(&[9, 4]);
+    state.seats[Player::Opponent].rounds_won = 1;
+    state.game_phase = GamePhase::Turn {
+        seat: Player::Player,
+    };
+    state
+}
+
+// Player drew past 20 and lost the round
+fn bust() -> GameState {
+    let mut state = GameState::new();
+    state.seats[Player::Player].dealer_row = cards(&[8, 6, 9]);
+    state.seats[Player::Player].bust = true;
+    state.seats[Player::Opponent].dealer_row = cards(&[10, 7]);
+    state.seats[Player::Opponent].played_row = cards(&[2]);
+    state.seats[Player::Opponent].hand = hand(&[Some(2), None, Some(1), Some(4)]);
+    state.seats[Player::Opponent].stood = true;
+    state.seats[Player::Opponent].rounds_won = 1;
+    state.round_outcome = Some(RoundOutcome::OpponentWon);
+    state.game_phase = GamePhase::AwaitingNextRound;
+    state
+}
+
+// Player took the third round
+fn game_over() -> GameState {
+    let mut state = GameState::new();
+    state.seats[Player::Player].dealer_row = cards(&[10, 6, 4]);
+    state.seats[Player::Player].stood = true;
+    state.seats[Player::Player].rounds_won = 3;
+    state.seats[Player::Opponent].dealer_row = cards(&[9, 9]);
+    state.seats[Player::Opponent].stood = true;
+    state.seats[Player::Opponent].rounds_won = 2;
+    state.round_outcome = Some(RoundOutcome::PlayerWon);
+    state.game_phase = GamePhase::GameOver {
+        winner: Player::Player,
+    };
+    state
+}
+
+// More dealer cards than fit in the dealer area at full size
+fn crowded_dealer_row() -> GameState {
+    let mut state = mid_round();
+    state.seats[Player::Player].dealer_row = cards(&[1; 12]);
+    state
+}
+
+// Hot-seat, Player 2 on 13 to play after Player 1 drew
+fn hot_seat(game_phase: GamePhase) -> GameState {
+    let mut state = GameState::hot_seat();
+    state.seats[Player::Player].dealer_row = cards(&[7, 5]);
+    state.seats[Player::Player].played_row = cards(&[3]);
+    state.seats[Player::Player].hand = hand(&[Some(5), None, Some(6), Some(2)]);
+    state.seats[Player::Opponent].dealer_row = cards(&[9, 4]);
+    state.game_phase = game_phase;
+    state
+}
+
+#[test]
+fn start_menu() {
+    for (cols, rows) in [(80, 24), (43, 11)] {
+        let config = Config::from_size(cols, rows);
+        let mut app = App::with_settings(config.clone(), settings());
+
+        let mut frame = new_frame(&config);
+        app.draw(&mut frame);
+
+        assert_golden(&format!("start_menu_{cols}x{rows}"), &render_text(&frame));
+    }
+}
+
+#[test]
+fn how_to_play_last_page() {
+    for (cols, rows) in [(80, 24), (43, 11)] {
+        let config = Config::from_size(cols, rows);
+        let mut app = App::with_settings(config.clone(), settings());
+
+        // How To Play is the fourth menu item, and its last page the longest
+        for key in "sss ddddddd".chars() {
+            let key = match key {
+                ' ' => KeyCode::Enter,
+                c => KeyCode::Char(c),
+            };
+            app.handle_key(key);
+        }
+
+        let mut frame = new_frame(&config);
+        app.draw(&mut frame);
+        assert_golden(
+            &format!("how_to_play_last_page_{cols}x{rows}"),
+            &render_text(&frame),
+        );
+    }
+}
+
+#[test]
+fn mid_round_board() {
+    for (cols, rows) in [(100, 30), (60, 20), (43, 11)] {
+        let config = Config::from_size(cols, rows);
+        let text = draw_board(&mid_round(), &config);
+
+        assert_golden(&format!("mid_round_{cols}x{rows}"), &text);
+    }
+}
+
+#[test]
+fn bust_board() {
+    let config = Config::from_size(100, 30);
+    assert_golden("bust_100x30", &draw_board(&bust(), &config));
+}
+
+#[test]
+fn game_over_board() {
+    let config = Config::from_size(100, 30);
+    assert_golden("game_over_100x30", &draw_board(&game_over(), &config));
+}
+
+#[test]
+fn crowded_dealer_row_board() {
+    for (cols, rows) in [(100, 30), (43, 11)] {
+        let config = Config::from_size(cols, rows);
+        let text = draw_board(&crowded_dealer_row(), &config);
+
+        assert_golden(&format!("crowded_dealer_row_{cols}x{rows}"), &text);
+    }
+}
+
+#[test]
+fn hot_seat_board() {
+    let config = Config::from_size(100, 30);
+
+    let handover = hot_seat(GamePhase::Handover {
+        to: Player::Opponent,
+    });
+    assert_golden("hot_seat_handover_100x30", &draw_board(&handover, &config));
+
+    let second_turn = hot_seat(GamePhase::Turn {
+        seat: Player::Opponent,
+    });
+    assert_golden("hot_seat_turn_100x30", &draw_board(&second_turn, &config));
+}
+
+#[test]
+fn hot_seat_chat() {
+    let config = Config::from_size(100, 30);
+    let mut app = App::with_settings(config.clone(), settings());
+    app.start_game(hot_seat(GamePhase::Turn {
+        seat: Player::Opponent,
+    }));
+
+    // Said, then typed into the open line where 'd' doesn't draw a card
+    for key in "tgg\ntd".chars() {
+        let key = match key {
+            '\n' => KeyCode::Enter,
+            c => KeyCode::Char(c),
+        };
+        app.handle_key(key);
+    }
+    assert!(app.is_typing());
+
+    let mut frame = new_frame(&config);
+    app.draw(&mut frame);
+    assert_golden("hot_seat_chat_100x30", &render_text(&frame));
+}
+
+#[test]
+fn hot_seat_chat_beside_the_outcome() {
+    let config = Config::from_size(100, 30);
+    let mut app = App::with_settings(config.clone(), settings());
+    let mut state = hot_seat(GamePhase::AwaitingNextRound);
+    state.round_outcome = Some(RoundOutcome::PlayerWon);
+    app.start_game(state);
+
+    // Four said, only the newest three fit
+    for key in "tfirst\ntgg\nt1\ntone more\n".chars() {
+        let key = match key {
+            '\n' => KeyCode::Enter,
+            c => KeyCode::Char(c),
+        };
+        app.handle_key(key);
+    }
+
+    let mut frame = new_frame(&config);
+    app.draw(&mut frame);
+    assert_golden("hot_seat_chat_outcome_100x30", &render_text(&frame));
+}
@@ -0,0 +1,74 @@
+// The scripted tutorial, played through step by step
+
+use kaazap::{TARGET_SCORE, game::GameState, player::Player, tutorial::TutorialState};
+
+// Let the scripted opponent move until it's waiting on the player again
+fn wait_for_opponent(state: &mut GameState) {
+    while state.next_deadline().is_some() {
+        state.skip_thinking();
+        state.update();
+    }
+}
+
+// Press `key` for the current step, which must accept it
+fn press(tutorial: &mut TutorialState, key: char) {
+    tutorial.handle_game_input(key);
+    let overlay = tutorial.overlay_text();
+    assert!(
+        !overlay.iter().any(|line| line.starts_with("Not now")),
+        "'{key}' was blocked: {overlay:?}"
+    );
+    wait_for_opponent(&mut tutorial.game_state);
+}
+
+#[test]
+fn every_step_teaches_the_best_move() {
+    let mut tutorial = TutorialState::new();
+
+    // Round one: draw twice, then the 6 makes exactly 20
+    press(&mut tutorial, 'd');
+    press(&mut tutorial, 'd');
+    assert_eq!(tutorial.game_state.seats[Player::Player].score(), 14);
+    press(&mut tutorial, '1');
+    assert_eq!(
+        tutorial.game_state.seats[Player::Player].score(),
+        TARGET_SCORE
+    );
+    press(&mut tutorial, 'n');
+
+    // Round two: on 17 no side card left helps, so standing is right
+    press(&mut tutorial, 'd');
+    press(&mut tutorial, 'd');
+    let player = &tutorial.game_state.seats[Player::Player];
+    assert_eq!(player.score(), 17);
+    assert!(
+        player
+            .hand
+            .iter()
+            .flatten()
+            .all(|card| player.score() + card.value > TARGET_SCORE)
+    );
+    press(&mut tutorial, 's');
+    assert!(tutorial.game_state.seats[Player::Opponent].bust);
+    press(&mut tutorial, 'n');
+
+    assert!(tutorial.overlay_text()[0].starts_with("Tutorial complete!"));
+}
+
+#[test]
+fn wrong_moves_are_explained() {
+    let mut tutorial = TutorialState::new();
+
+    tutorial.handle_game_input('s');
+
+    let overlay = tutorial.overlay_text();
+    assert_eq!(
+        overlay.last().unwrap(),
+        "Not now: Every turn starts by drawing a dealer card. Press 'd'."
+    );
+    assert!(
+        tutorial.game_state.seats[Player::Player]
+            .dealer_row
+            .is_empty()
+    );
+}